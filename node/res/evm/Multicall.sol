@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity 0.8.11;
+
+/// @title Batches read-only calls into one, predeployed in the Indranet development specs.
+/// @dev Predeployed from its compiled runtime bytecode, built with solc 0.8.11 as
+/// `solc --optimize --optimize-runs 200 --evm-version london --bin-runtime -o <dir> Multicall.sol`
+/// and passed as `<dir>` to `build-spec --evm-bytecode`.
+contract Multicall {
+	struct Call {
+		address target;
+		bytes callData;
+	}
+
+	/// @dev Execute `calls` in order, reverting if any of them fails.
+	function aggregate(Call[] calldata calls)
+		external
+		returns (uint256 blockNumber, bytes[] memory returnData)
+	{
+		blockNumber = block.number;
+		returnData = new bytes[](calls.length);
+		for (uint256 i = 0; i < calls.length; i++) {
+			(bool success, bytes memory ret) = calls[i].target.call(calls[i].callData);
+			require(success, "Multicall: call failed");
+			returnData[i] = ret;
+		}
+	}
+
+	function getBlockHash(uint256 blockNumber) external view returns (bytes32) {
+		return blockhash(blockNumber);
+	}
+
+	function getCurrentBlockTimestamp() external view returns (uint256) {
+		return block.timestamp;
+	}
+
+	function getEthBalance(address account) external view returns (uint256) {
+		return account.balance;
+	}
+}
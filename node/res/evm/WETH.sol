@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity 0.8.11;
+
+/// @title A WETH9 style ERC-20 wrapper of SEL, predeployed in the Indranet development specs.
+/// @dev Predeployed from its compiled runtime bytecode, built with solc 0.8.11 as
+/// `solc --optimize --optimize-runs 200 --evm-version london --bin-runtime -o <dir> WETH.sol`
+/// and passed as `<dir>` to `build-spec --evm-bytecode`.
+contract WETH {
+	string public constant name = "Wrapped SEL";
+	string public constant symbol = "WSEL";
+	uint8 public constant decimals = 18;
+
+	event Approval(address indexed owner, address indexed spender, uint256 value);
+	event Transfer(address indexed from, address indexed to, uint256 value);
+	event Deposit(address indexed to, uint256 value);
+	event Withdrawal(address indexed from, uint256 value);
+
+	mapping(address => uint256) public balanceOf;
+	mapping(address => mapping(address => uint256)) public allowance;
+
+	receive() external payable {
+		deposit();
+	}
+
+	function deposit() public payable {
+		balanceOf[msg.sender] += msg.value;
+		emit Deposit(msg.sender, msg.value);
+	}
+
+	function withdraw(uint256 value) external {
+		require(balanceOf[msg.sender] >= value, "WETH: insufficient balance");
+		balanceOf[msg.sender] -= value;
+		payable(msg.sender).transfer(value);
+		emit Withdrawal(msg.sender, value);
+	}
+
+	function totalSupply() external view returns (uint256) {
+		return address(this).balance;
+	}
+
+	function approve(address spender, uint256 value) external returns (bool) {
+		allowance[msg.sender][spender] = value;
+		emit Approval(msg.sender, spender, value);
+		return true;
+	}
+
+	function transfer(address to, uint256 value) external returns (bool) {
+		return transferFrom(msg.sender, to, value);
+	}
+
+	function transferFrom(address from, address to, uint256 value) public returns (bool) {
+		require(balanceOf[from] >= value, "WETH: insufficient balance");
+		if (from != msg.sender && allowance[from][msg.sender] != type(uint256).max) {
+			require(allowance[from][msg.sender] >= value, "WETH: insufficient allowance");
+			allowance[from][msg.sender] -= value;
+		}
+		balanceOf[from] -= value;
+		balanceOf[to] += value;
+		emit Transfer(from, to, value);
+		return true;
+	}
+}
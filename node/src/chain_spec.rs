@@ -16,16 +16,22 @@
 
 use cumulus_primitives_core::ParaId;
 use hex_literal::hex;
-use parachains_common::{AccountId, AuraId, Signature};
+use pallet_evm::GenesisAccount;
+use parachains_common::{AccountId, AuraId, Balance, BlockNumber, Signature};
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
-use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
+use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public, H160};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::{collections::BTreeMap, path::Path};
 
 /// Specialized `ChainSpec` for the normal parachain runtime.
 pub type ChainSpec = sc_service::GenericChainSpec<indracore_runtime::GenesisConfig, Extensions>;
 
+/// A genesis vesting schedule: the account, the block its vesting begins, the number of blocks it
+/// vests over and the amount of its genesis balance which is liquid from the start.
+pub type GenesisVesting = (AccountId, BlockNumber, BlockNumber, Balance);
+
 /// The default XCM version to set in genesis config.
 const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
 
@@ -126,6 +132,7 @@ pub fn indracore_development_config() -> IndracoreChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				Vec::new(),
 				1000.into(),
 			)
 		},
@@ -177,6 +184,7 @@ pub fn indracore_local_config() -> IndracoreChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				Vec::new(),
 				1000.into(),
 			)
 		},
@@ -231,6 +239,7 @@ pub fn indracore_config() -> IndracoreChainSpec {
 					),
 				],
 				Vec::new(),
+				Vec::new(),
 				1000.into(),
 			)
 		},
@@ -246,8 +255,11 @@ pub fn indracore_config() -> IndracoreChainSpec {
 fn indracore_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
+	vesting: Vec<GenesisVesting>,
 	id: ParaId,
 ) -> indracore_runtime::GenesisConfig {
+	// The initial collators also seed the council and the technical committee.
+	let governors: Vec<AccountId> = invulnerables.iter().map(|(acc, _)| acc.clone()).collect();
 	indracore_runtime::GenesisConfig {
 		system: indracore_runtime::SystemConfig {
 			code: indracore_runtime::WASM_BINARY
@@ -281,15 +293,32 @@ fn indracore_genesis(
 		selendra_xcm: indracore_runtime::SelendraXcmConfig {
 			safe_xcm_version: Some(SAFE_XCM_VERSION),
 		},
+		treasury: Default::default(),
+		vesting: indracore_runtime::VestingConfig { vesting },
+		assets: indracore_runtime::AssetsConfig {
+			assets: vec![],
+			metadata: vec![],
+			accounts: vec![],
+		},
+		democracy: Default::default(),
+		council_membership: indracore_runtime::CouncilMembershipConfig {
+			members: governors.clone(),
+			phantom: Default::default(),
+		},
+		technical_membership: indracore_runtime::TechnicalMembershipConfig {
+			members: governors,
+			phantom: Default::default(),
+		},
 	}
 }
 
-pub fn indranet_development_config() -> IndranetChainSpec {
+pub fn indranet_development_config(bytecode: Option<&Path>) -> Result<IndranetChainSpec, String> {
+	let evm_accounts = crate::predeploys::genesis_accounts(bytecode)?;
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "CDM".into());
 	properties.insert("tokenDecimals".into(), 18.into());
 
-	IndranetChainSpec::from_genesis(
+	Ok(IndranetChainSpec::from_genesis(
 		// Name
 		"Indranet Development",
 		// ID
@@ -308,6 +337,8 @@ pub fn indranet_development_config() -> IndranetChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				Vec::new(),
+				evm_accounts.clone(),
 				1000.into(),
 			)
 		},
@@ -317,15 +348,16 @@ pub fn indranet_development_config() -> IndranetChainSpec {
 		None,
 		Some(properties),
 		Extensions { relay_chain: "cardamom-dev".into(), para_id: 1000 },
-	)
+	))
 }
 
-pub fn indranet_local_config() -> IndranetChainSpec {
+pub fn indranet_local_config(bytecode: Option<&Path>) -> Result<IndranetChainSpec, String> {
+	let evm_accounts = crate::predeploys::genesis_accounts(bytecode)?;
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "CDM".into());
 	properties.insert("tokenDecimals".into(), 18.into());
 
-	IndranetChainSpec::from_genesis(
+	Ok(IndranetChainSpec::from_genesis(
 		// Name
 		"Indranet Local",
 		// ID
@@ -358,6 +390,8 @@ pub fn indranet_local_config() -> IndranetChainSpec {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				Vec::new(),
+				evm_accounts.clone(),
 				1000.into(),
 			)
 		},
@@ -367,7 +401,7 @@ pub fn indranet_local_config() -> IndranetChainSpec {
 		None,
 		Some(properties),
 		Extensions { relay_chain: "cardamom-local".into(), para_id: 1000 },
-	)
+	))
 }
 
 pub fn indranet_config() -> IndranetChainSpec {
@@ -411,6 +445,8 @@ pub fn indranet_config() -> IndranetChainSpec {
 					),
 				],
 				Vec::new(),
+				Vec::new(),
+				BTreeMap::new(),
 				1000.into(),
 			)
 		},
@@ -426,6 +462,8 @@ pub fn indranet_config() -> IndranetChainSpec {
 fn indranet_genesis(
 	invulnerables: Vec<(AccountId, AuraId)>,
 	endowed_accounts: Vec<AccountId>,
+	vesting: Vec<GenesisVesting>,
+	evm_accounts: BTreeMap<H160, GenesisAccount>,
 	id: ParaId,
 ) -> indranet_runtime::GenesisConfig {
 	let root_key = hex!("c824993c8b7bbd6956b2fb4e7a884faa82b58699008aa9dc5708e7086798410b").into();
@@ -464,6 +502,16 @@ fn indranet_genesis(
 		selendra_xcm: indranet_runtime::SelendraXcmConfig {
 			safe_xcm_version: Some(SAFE_XCM_VERSION),
 		},
+		treasury: Default::default(),
+		vesting: indranet_runtime::VestingConfig { vesting },
+		assets: indranet_runtime::AssetsConfig {
+			assets: vec![],
+			metadata: vec![],
+			accounts: vec![],
+		},
 		sudo: indranet_runtime::SudoConfig { key: Some(root_key) },
+		evm: indranet_runtime::EVMConfig { accounts: evm_accounts },
+		ethereum: Default::default(),
+		base_fee: Default::default(),
 	}
 }
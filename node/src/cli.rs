@@ -31,7 +31,7 @@ pub enum Subcommand {
 	ExportGenesisWasm(ExportGenesisWasmCommand),
 
 	/// Build a chain specification.
-	BuildSpec(sc_cli::BuildSpecCmd),
+	BuildSpec(BuildSpecCommand),
 
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
@@ -62,6 +62,20 @@ pub enum Subcommand {
 	Key(sc_cli::KeySubcommand),
 }
 
+/// Command for building a chain specification.
+#[derive(Debug, StructOpt)]
+pub struct BuildSpecCommand {
+	#[structopt(flatten)]
+	pub base: sc_cli::BuildSpecCmd,
+
+	/// Directory of `solc --bin-runtime` output to predeploy contracts from.
+	///
+	/// Only the Indranet development specs have predeploys, and only when this is given. A
+	/// contract compiled from `node/res/evm` is left out when its bytecode isn't in the directory.
+	#[structopt(long, parse(from_os_str))]
+	pub evm_bytecode: Option<PathBuf>,
+}
+
 /// Command for exporting the genesis state of the parachain
 #[derive(Debug, StructOpt)]
 pub struct ExportGenesisStateCommand {
@@ -113,6 +127,16 @@ pub struct Cli {
 	#[structopt(flatten)]
 	pub run: cumulus_client_cli::RunCmd,
 
+	/// Serve `debug_traceTransaction` and `debug_traceBlockByNumber` on indranet nodes, which
+	/// re-execute blocks to trace their EVM transactions. The RPC is unsafe, and needs a node
+	/// built with `--features evm-tracing`.
+	///
+	/// Blocks are traced with the runtime they were built with, which only supports tracing if
+	/// it was built with `--features evm-tracing` too. Otherwise pass `--wasm-runtime-overrides`
+	/// a directory holding such a build of each runtime version to trace.
+	#[structopt(long)]
+	pub enable_evm_tracing: bool,
+
 	/// Relay chain arguments
 	#[structopt(raw = true)]
 	pub relaychain_args: Vec<String>,
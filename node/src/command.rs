@@ -22,7 +22,7 @@ use crate::{
 use codec::Encode;
 use cumulus_client_service::genesis::generate_genesis_block;
 use cumulus_primitives_core::ParaId;
-use log::info;
+use log::{info, warn};
 use parachains_common::AuraId;
 use sc_cli::{
 	ChainSpec, CliConfiguration, DefaultConfigurationValues, ImportParams, KeystoreParams,
@@ -35,7 +35,7 @@ use sc_service::{
 use selendra_parachain::primitives::AccountIdConversion;
 use sp_core::{crypto::Ss58AddressFormat, hexdisplay::HexDisplay};
 use sp_runtime::traits::Block as BlockT;
-use std::{io::Write, net::SocketAddr};
+use std::{io::Write, net::SocketAddr, path::Path};
 
 trait IdentifyChain {
 	fn is_indracore(&self) -> bool;
@@ -60,7 +60,12 @@ impl<T: sc_service::ChainSpec + 'static> IdentifyChain for T {
 	}
 }
 
-fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+/// Load the chain spec `id`, predeploying the contracts compiled to `bytecode` in the Indranet
+/// development specs.
+fn load_spec(
+	id: &str,
+	bytecode: Option<&Path>,
+) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
 	Ok(match id {
 		// -- Indracore
 		"indracore-dev" => Box::new(chain_spec::indracore_development_config()),
@@ -72,8 +77,8 @@ fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, St
 			&include_bytes!("../res/indracore.json")[..],
 		)?),
 		// -- Indranet
-		"indranet-dev" => Box::new(chain_spec::indranet_development_config()),
-		"indranet-local" => Box::new(chain_spec::indranet_local_config()),
+		"indranet-dev" => Box::new(chain_spec::indranet_development_config(bytecode)?),
+		"indranet-local" => Box::new(chain_spec::indranet_local_config(bytecode)?),
 		// the chain spec as used for generating the upgrade genesis values
 		"indranet-staging" => Box::new(chain_spec::indranet_config()),
 		// the shell-based chain spec as used for syncing
@@ -125,7 +130,7 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-		load_spec(id)
+		load_spec(id, None)
 	}
 
 	fn native_runtime_version(chain_spec: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -223,8 +228,15 @@ pub fn run() -> Result<()> {
 
 	match &cli.subcommand {
 		Some(Subcommand::BuildSpec(cmd)) => {
-			let runner = cli.create_runner(cmd)?;
-			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
+			let runner = cli.create_runner(&cmd.base)?;
+			runner.sync_run(|mut config| {
+				if let Some(bytecode) = cmd.evm_bytecode.as_deref() {
+					let shared_params = &cmd.base.shared_params;
+					config.chain_spec =
+						load_spec(&shared_params.chain_id(shared_params.dev), Some(bytecode))?;
+				}
+				cmd.base.run(config.chain_spec, config.network)
+			})
 		},
 		Some(Subcommand::CheckBlock(cmd)) => {
 			set_default_ss58_version();
@@ -280,7 +292,7 @@ pub fn run() -> Result<()> {
 			builder.with_profiling(sc_tracing::TracingReceiver::Log, "");
 			let _ = builder.init();
 
-			let spec = load_spec(&params.chain.clone().unwrap_or_default())?;
+			let spec = load_spec(&params.chain.clone().unwrap_or_default(), None)?;
 			let state_version = Cli::native_runtime_version(&spec).state_version();
 
 			let block: crate::service::Block = generate_genesis_block(&spec, state_version)?;
@@ -363,6 +375,11 @@ pub fn run() -> Result<()> {
 		},
 		Some(Subcommand::Key(cmd)) => Ok(cmd.run(&cli)?),
 		None => {
+			if cli.enable_evm_tracing && !cfg!(feature = "evm-tracing") {
+				return Err("EVM tracing wasn't enabled when building the node. \
+				You can enable it with `--features evm-tracing`."
+					.into())
+			}
 			let runner = cli.create_runner(&cli.run.normalize())?;
 			set_default_ss58_version();
 			runner.run_node_until_exit(|config| async move {
@@ -401,11 +418,18 @@ pub fn run() -> Result<()> {
 				info!("Is collating: {}", if config.role.is_authority() { "yes" } else { "no" });
 
 				if config.chain_spec.is_indranet() {
-					crate::service::start_indracore_node::<
-						indranet_runtime::RuntimeApi,
-						IndranetRuntimeExecutor,
-						AuraId,
-					>(config, selendra_config, id)
+					if cli.enable_evm_tracing && config.wasm_runtime_overrides.is_none() {
+						warn!(
+							"EVM tracing needs a runtime built with `--features evm-tracing`. \
+							Unless the chain runs one, pass it with `--wasm-runtime-overrides`."
+						);
+					}
+					crate::service::start_indranet_node(
+						config,
+						selendra_config,
+						id,
+						cli.enable_evm_tracing,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
@@ -414,7 +438,13 @@ pub fn run() -> Result<()> {
 						indracore_runtime::RuntimeApi,
 						IndracoreRuntimeExecutor,
 						AuraId,
-					>(config, selendra_config, id)
+						_,
+					>(
+						config,
+						selendra_config,
+						id,
+						crate::rpc::create_full::<_, _, indracore_runtime::IdentityRegistration>,
+					)
 					.await
 					.map(|r| r.0)
 					.map_err(Into::into)
@@ -0,0 +1,183 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+
+// Cumulus is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Cumulus is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The EVM accounts and contracts created in the genesis of the Indranet development specs.
+//!
+//! Predeploys are opt-in, through the solc output given to `build-spec --evm-bytecode`. The
+//! contracts compiled from the sources in `res/evm` are deployed from that output only, so that
+//! their genesis code is what solc makes of the source.
+
+use pallet_evm::GenesisAccount;
+use serde::Deserialize;
+use sp_core::{Bytes, H160, H256, U256};
+use std::{collections::BTreeMap, path::Path};
+
+/// The predeploy files, kept next to the chain specs.
+const PREDEPLOYS: &[&str] = &[
+	include_str!("../res/evm/create2.json"),
+	include_str!("../res/evm/multicall.json"),
+	include_str!("../res/evm/weth.json"),
+];
+
+/// An EVM account to create in genesis, as declared in a predeploy file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Predeploy {
+	/// The name of the contract, which is also the name solc gives its compiled bytecode.
+	name: String,
+	address: H160,
+	#[serde(default)]
+	nonce: U256,
+	#[serde(default)]
+	balance: U256,
+	#[serde(default)]
+	storage: BTreeMap<H256, H256>,
+	/// The runtime bytecode of a contract without a source in the tree, such as the canonical
+	/// CREATE2 deployer.
+	#[serde(default)]
+	code: Option<Bytes>,
+}
+
+/// The genesis EVM accounts of the predeploys, none unless `bytecode` is given.
+///
+/// The code of a predeploy is read from its `<name>.bin-runtime` in `bytecode` if there is one,
+/// and otherwise taken from its file. Predeploys without code are left out.
+pub fn genesis_accounts(bytecode: Option<&Path>) -> Result<BTreeMap<H160, GenesisAccount>, String> {
+	let mut accounts = BTreeMap::new();
+	let bytecode = match bytecode {
+		Some(bytecode) => bytecode,
+		None => return Ok(accounts),
+	};
+	for predeploy in PREDEPLOYS {
+		let Predeploy { name, address, nonce, balance, storage, code } =
+			serde_json::from_str(predeploy).expect("predeploy files are valid; qed");
+		let compiled = bytecode.join(format!("{}.bin-runtime", name));
+		let code = match (compiled.exists(), code) {
+			(true, _) => read_bytecode(&compiled)?,
+			(false, Some(code)) => code.0,
+			(false, None) => {
+				log::info!("No bytecode for the {} predeploy, leaving it out", name);
+				continue
+			},
+		};
+		accounts.insert(address, GenesisAccount { nonce, balance, storage, code });
+	}
+	Ok(accounts)
+}
+
+/// Read the hex encoded bytecode solc wrote to `path`.
+fn read_bytecode(path: &Path) -> Result<Vec<u8>, String> {
+	let hex = std::fs::read_to_string(path)
+		.map_err(|e| format!("Error reading {}: {}", path.display(), e))?;
+	let hex = hex.trim();
+	sp_core::bytes::from_hex(&format!("0x{}", hex.strip_prefix("0x").unwrap_or(hex)))
+		.map_err(|e| format!("Invalid bytecode in {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use indranet_runtime::{constants::currency::UNITS, Balances, Origin, Runtime};
+	use pallet_evm::{AddressMapping, ExitReason, Runner};
+	use sp_runtime::BuildStorage;
+
+	const WETH: H160 = H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x10, 0x01]);
+
+	#[test]
+	fn predeploys_are_opt_in() {
+		assert!(genesis_accounts(None).unwrap().is_empty());
+	}
+
+	#[test]
+	fn predeploys_are_deployed_from_compiled_bytecode() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("WETH.bin-runtime"), "6080604052\n").unwrap();
+
+		let accounts = genesis_accounts(Some(dir.path())).unwrap();
+		let create2 = "4e59b44847b379578588920ca78fbf26c0b4956c".parse().unwrap();
+
+		// The multicall contract isn't compiled, so it is left out.
+		assert_eq!(accounts.len(), 2);
+		assert!(!accounts[&create2].code.is_empty());
+		assert_eq!(accounts[&WETH].code, vec![0x60, 0x80, 0x60, 0x40, 0x52]);
+	}
+
+	/// Call `signature` of the WETH predeploy as `from`, with `args` as its ABI words.
+	fn call_weth(from: H160, signature: &str, args: &[U256], value: u128) -> Vec<u8> {
+		let mut input = sp_core::hashing::keccak_256(signature.as_bytes())[..4].to_vec();
+		for arg in args {
+			let mut word = [0u8; 32];
+			arg.to_big_endian(&mut word);
+			input.extend_from_slice(&word);
+		}
+
+		let info = <Runtime as pallet_evm::Config>::Runner::call(
+			from,
+			WETH,
+			input,
+			value.into(),
+			1_000_000,
+			None,
+			None,
+			None,
+			vec![],
+			<Runtime as pallet_evm::Config>::config(),
+		)
+		.expect("the call is valid");
+		assert!(matches!(info.exit_reason, ExitReason::Succeed(_)), "{} failed", signature);
+		info.value
+	}
+
+	fn weth_balance_of(who: H160) -> U256 {
+		let who = U256::from_big_endian(who.as_bytes());
+		U256::from_big_endian(&call_weth(H160::zero(), "balanceOf(address)", &[who], 0))
+	}
+
+	/// Runs the WETH predeploy as built from its source into the directory in
+	/// `INDRANET_EVM_BYTECODE`, since solc output isn't kept in the tree:
+	/// `INDRANET_EVM_BYTECODE=<dir> cargo test -p selendra-collator -- --ignored`.
+	#[test]
+	#[ignore]
+	fn weth_deposits_withdraws_and_transfers() {
+		let dir = std::env::var_os("INDRANET_EVM_BYTECODE").expect("INDRANET_EVM_BYTECODE is set");
+		let storage = crate::chain_spec::indranet_development_config(Some(Path::new(&dir)))
+			.unwrap()
+			.build_storage()
+			.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			let (alice, bob) = (H160::repeat_byte(0xaa), H160::repeat_byte(0xbb));
+			let account = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(alice);
+			assert!(Balances::set_balance(Origin::root(), account.into(), 10 * UNITS, 0).is_ok());
+
+			call_weth(alice, "deposit()", &[], 4 * UNITS);
+			call_weth(alice, "withdraw(uint256)", &[UNITS.into()], 0);
+			let to = U256::from_big_endian(bob.as_bytes());
+			call_weth(alice, "transfer(address,uint256)", &[to, UNITS.into()], 0);
+
+			assert_eq!(weth_balance_of(alice), (2 * UNITS).into());
+			assert_eq!(weth_balance_of(bob), UNITS.into());
+			assert_eq!(
+				pallet_evm::Pallet::<Runtime>::account_basic(&WETH).balance,
+				(3 * UNITS).into()
+			);
+			assert_eq!(
+				pallet_evm::Pallet::<Runtime>::account_basic(&alice).balance,
+				(7 * UNITS).into()
+			);
+		});
+	}
+}
@@ -20,14 +20,23 @@
 
 use std::sync::Arc;
 
-use sc_client_api::AuxStore;
+use codec::Codec;
+
+use fc_rpc::{EthBlockDataCache, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
+use sc_client_api::{
+	AuxStore, Backend, BlockBackend, BlockchainEvents, StateBackend, StorageProvider,
+};
+use sc_network::NetworkService;
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
+use sc_transaction_pool::{ChainApi, Pool};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_runtime::traits::BlakeTwo256;
 
-use parachains_common::{AccountId, Balance, Block, Index as Nonce};
+use parachains_common::{AccountId, Balance, Block, Hash, Index as Nonce};
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
@@ -38,12 +47,53 @@ pub struct FullDeps<C, P> {
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// Network service
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Whether the node is running as a collator
+	pub is_authority: bool,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
 }
 
-/// Instantiate all RPC extensions.
-pub fn create_full<C, P>(deps: FullDeps<C, P>) -> RpcExtension
+/// Ethereum client dependencies, shared by every RPC connection of an EVM runtime.
+pub struct EthDeps<A: ChainApi> {
+	/// Graph pool instance.
+	pub graph: Arc<Pool<A>>,
+	/// Frontier backend mapping Ethereum blocks and transactions to Substrate ones.
+	pub backend: Arc<fc_db::Backend<Block>>,
+	/// Storage overrides for each Ethereum storage schema.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Installed log filters.
+	pub filter_pool: FilterPool,
+	/// Maximum number of stored log filters.
+	pub max_stored_filters: usize,
+	/// Maximum number of logs returned by a single query.
+	pub max_past_logs: u32,
+	/// Maximum number of blocks served by `eth_feeHistory`.
+	pub fee_history_limit: u64,
+	/// Fee history cache, kept up to date by `EthTask::fee_history_task`.
+	pub fee_history_cache: FeeHistoryCache,
+	/// Cache of Ethereum blocks and their transaction statuses.
+	pub block_data_cache: Arc<EthBlockDataCache<Block>>,
+	/// Whether to serve the `debug_trace*` APIs, which re-execute blocks. Only nodes built with
+	/// `evm-tracing` serve them.
+	pub tracing: bool,
+}
+
+/// The runtime API of the `debug_trace*` APIs, which runtimes built with `evm-tracing` implement.
+#[cfg(feature = "evm-tracing")]
+pub trait EvmTracingApi: fp_rpc::DebugRuntimeApi<Block> {}
+#[cfg(feature = "evm-tracing")]
+impl<T: fp_rpc::DebugRuntimeApi<Block>> EvmTracingApi for T {}
+
+/// The runtime API of the `debug_trace*` APIs, which nodes built without `evm-tracing` don't need.
+#[cfg(not(feature = "evm-tracing"))]
+pub trait EvmTracingApi {}
+#[cfg(not(feature = "evm-tracing"))]
+impl<T> EvmTracingApi for T {}
+
+/// Instantiate all RPC extensions, for a runtime whose identity records are `Registration`.
+pub fn create_full<C, P, Registration>(deps: FullDeps<C, P>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>
 		+ HeaderBackend<Block>
@@ -54,17 +104,131 @@ where
 		+ 'static,
 	C::Api: frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_studentid_rpc::IdentityRuntimeApi<Block, AccountId, Registration>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
+	Registration: Codec + Send + Sync + 'static,
 {
 	use frame_rpc_system::{FullSystem, SystemApi};
+	use pallet_studentid_rpc::{Identity, IdentityApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, .. } = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone())));
+	io.extend_with(IdentityApi::to_delegate(Identity::<_, _, Registration>::new(client)));
+
+	io
+}
+
+/// Instantiate all RPC extensions of [`create_full`], together with the Ethereum
+/// `eth_*`, `net_*` and `web3_*` APIs, `eth_subscribe`, and the `debug_trace*` APIs if
+/// tracing is enabled.
+pub fn create_full_with_eth<C, P, BE, A, Registration>(
+	deps: FullDeps<C, P>,
+	eth: EthDeps<A>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+) -> RpcExtension
+where
+	BE: Backend<Block> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+	C: ProvideRuntimeApi<Block>
+		+ StorageProvider<Block, BE>
+		+ BlockchainEvents<Block>
+		+ BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ AuxStore
+		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_studentid_rpc::IdentityRuntimeApi<Block, AccountId, Registration>,
+	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	C::Api: EvmTracingApi,
+	C::Api: BlockBuilder<Block>,
+	P: TransactionPool<Block = Block> + Sync + Send + 'static,
+	A: ChainApi<Block = Block> + 'static,
+	Registration: Codec + Send + Sync + 'static,
+{
+	use fc_rpc::{
+		format::Geth, EthApi, EthApiServer, EthFilterApi, EthFilterApiServer, EthPubSubApi,
+		EthPubSubApiServer, HexEncodedIdProvider, NetApi, NetApiServer, Web3Api, Web3ApiServer,
+	};
+	#[cfg(feature = "evm-tracing")]
+	use fc_rpc::{DebugApi, DebugApiServer};
+	use jsonrpc_pubsub::manager::SubscriptionManager;
+
+	let client = deps.client.clone();
+	let pool = deps.pool.clone();
+	let network = deps.network.clone();
+	let is_authority = deps.is_authority;
+	let deny_unsafe = deps.deny_unsafe;
+	let EthDeps {
+		graph,
+		backend,
+		overrides,
+		filter_pool,
+		max_stored_filters,
+		max_past_logs,
+		fee_history_limit,
+		fee_history_cache,
+		block_data_cache,
+		tracing,
+	} = eth;
+
+	#[cfg(not(feature = "evm-tracing"))]
+	let _ = tracing;
+	let mut io = create_full::<C, P, Registration>(deps);
+
+	io.extend_with(EthApiServer::to_delegate(EthApi::new(
+		client.clone(),
+		pool.clone(),
+		graph,
+		network.clone(),
+		Vec::new(),
+		overrides.clone(),
+		backend.clone(),
+		is_authority,
+		max_past_logs,
+		block_data_cache.clone(),
+		Geth,
+		fee_history_limit,
+		fee_history_cache,
+	)));
+	#[cfg(feature = "evm-tracing")]
+	if tracing {
+		io.extend_with(DebugApiServer::to_delegate(DebugApi::<_, _, BE>::new(
+			client.clone(),
+			backend.clone(),
+			deny_unsafe,
+		)));
+	}
+	io.extend_with(EthFilterApiServer::to_delegate(EthFilterApi::new(
+		client.clone(),
+		backend,
+		filter_pool,
+		max_stored_filters,
+		overrides.clone(),
+		max_past_logs,
+		block_data_cache,
+	)));
+	io.extend_with(NetApiServer::to_delegate(NetApi::new(client.clone(), network.clone(), true)));
+	io.extend_with(Web3ApiServer::to_delegate(Web3Api::new(client.clone())));
+	io.extend_with(EthPubSubApiServer::to_delegate(EthPubSubApi::new(
+		pool,
+		client,
+		network,
+		SubscriptionManager::<HexEncodedIdProvider>::with_id_provider(
+			HexEncodedIdProvider::default(),
+			Arc::new(subscription_task_executor),
+		),
+		overrides,
+	)));
 
 	io
 }
@@ -31,19 +31,29 @@ use cumulus_relay_chain_interface::RelayChainInterface;
 use cumulus_relay_chain_local::build_relay_chain_interface;
 use selendra_service::NativeExecutionDispatch;
 
-use crate::rpc;
-pub use parachains_common::{AccountId, Balance, Block, Hash, Header, Index as Nonce};
+use crate::{cli::Cli, rpc};
+pub use parachains_common::{AccountId, AuraId, Balance, Block, Hash, Header, Index as Nonce};
 
 use cumulus_client_consensus_relay_chain::Verifier as RelayChainVerifier;
-use futures::lock::Mutex;
-use sc_client_api::ExecutorProvider;
+use fc_mapping_sync::{MappingSyncWorker, SyncStrategy};
+use fc_rpc::{
+	EthBlockDataCache, EthTask, OverrideHandle, RuntimeApiStorageOverride, SchemaV1Override,
+	SchemaV2Override, SchemaV3Override, StorageOverride,
+};
+use fc_rpc_core::types::{FeeHistoryCache, FilterPool};
+use futures::{lock::Mutex, StreamExt};
+use pallet_ethereum::EthereumStorageSchema;
+use sc_cli::SubstrateCli;
+use sc_client_api::{BlockchainEvents, ExecutorProvider};
 use sc_consensus::{
 	import_queue::{BasicQueue, Verifier as VerifierT},
 	BlockImportParams,
 };
 use sc_executor::NativeElseWasmExecutor;
 use sc_network::NetworkService;
-use sc_service::{Configuration, PartialComponents, Role, TFullBackend, TFullClient, TaskManager};
+use sc_service::{
+	BasePath, Configuration, PartialComponents, Role, TFullBackend, TFullClient, TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
 use sp_api::{ApiExt, ConstructRuntimeApi};
 use sp_consensus::{CacheKeyId, SlotData};
@@ -55,7 +65,7 @@ use sp_runtime::{
 	generic::BlockId,
 	traits::{BlakeTwo256, Header as HeaderT},
 };
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use std::{collections::BTreeMap, marker::PhantomData, path::PathBuf, sync::Arc, time::Duration};
 use substrate_prometheus_endpoint::Registry;
 
 /// Native Indracore executor instance.
@@ -205,12 +215,15 @@ where
 /// Start a node with the given parachain `Configuration` and relay chain `Configuration`.
 ///
 /// This is the actual implementation that is abstract over the executor and the runtime api.
+///
+/// `rpc_ext_builder` is called once the client is built, so that it can spawn the tasks its RPC
+/// extensions rely on, and returns the builder of the RPC extensions of each connection.
 #[sc_tracing::logging::prefix_logs_with("Parachain")]
-async fn start_node_impl<RuntimeApi, Executor, RB, BIQ, BIC>(
+async fn start_node_impl<RuntimeApi, Executor, RB, RBI, BIQ, BIC>(
 	parachain_config: Configuration,
 	selendra_config: Configuration,
 	id: ParaId,
-	_rpc_ext_builder: RB,
+	rpc_ext_builder: RB,
 	build_import_queue: BIQ,
 	build_consensus: BIC,
 ) -> sc_service::error::Result<(
@@ -235,9 +248,22 @@ where
 		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>: sp_api::StateBackend<BlakeTwo256>,
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
-	RB: Fn(
-			Arc<TFullClient<Block, RuntimeApi, Executor>>,
-		) -> Result<jsonrpc_core::IoHandler<sc_rpc::Metadata>, sc_service::Error>
+	RB: FnOnce(
+		&Configuration,
+		&TaskManager,
+		Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
+		Arc<TFullBackend<Block>>,
+	) -> Result<RBI, sc_service::Error>,
+	RBI: Fn(
+			rpc::FullDeps<
+				TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				sc_transaction_pool::FullPool<
+					Block,
+					TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				>,
+			>,
+			rpc::SubscriptionTaskExecutor,
+		) -> rpc::RpcExtension
 		+ Send
 		+ 'static,
 	BIQ: FnOnce(
@@ -282,6 +308,8 @@ where
 	let backend = params.backend.clone();
 
 	let mut task_manager = params.task_manager;
+	let rpc_ext_builder =
+		rpc_ext_builder(&parachain_config, &task_manager, client.clone(), backend.clone())?;
 	let (relay_chain_interface, collator_key) =
 		build_relay_chain_interface(selendra_config, telemetry_worker_handle, &mut task_manager)
 			.map_err(|e| match e {
@@ -312,15 +340,18 @@ where
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
+		let network = network.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
+				network: network.clone(),
+				is_authority: validator,
 				deny_unsafe,
 			};
 
-			Ok(rpc::create_full(deps))
+			Ok(rpc_ext_builder(deps, subscription_executor))
 		})
 	};
 
@@ -583,11 +614,14 @@ where
 	))
 }
 
-/// Start a indracore/indracore/indranet parachain node.
-pub async fn start_indracore_node<RuntimeApi, Executor, AuraId: AppKey>(
+/// Start a parachain node without an EVM, such as indracore.
+///
+/// `rpc_ext_builder` provides the RPC extensions served by the node, which depend on the runtime.
+pub async fn start_indracore_node<RuntimeApi, Executor, AuraId: AppKey, RB>(
 	parachain_config: Configuration,
 	selendra_config: Configuration,
 	id: ParaId,
+	rpc_ext_builder: RB,
 ) -> sc_service::error::Result<(
 	TaskManager,
 	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
@@ -613,12 +647,82 @@ where
 	Executor: sc_executor::NativeExecutionDispatch + 'static,
 	<<AuraId as AppKey>::Pair as Pair>::Signature:
 		TryFrom<Vec<u8>> + std::hash::Hash + sp_runtime::traits::Member + Codec,
+	RB: Fn(
+			rpc::FullDeps<
+				TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				sc_transaction_pool::FullPool<
+					Block,
+					TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				>,
+			>,
+		) -> rpc::RpcExtension
+		+ Send
+		+ 'static,
 {
-	start_node_impl::<RuntimeApi, Executor, _, _, _>(
+	start_aura_node::<RuntimeApi, Executor, AuraId, _, _>(
 		parachain_config,
 		selendra_config,
 		id,
-		|_| Ok(Default::default()),
+		move |_, _, _, _| Ok(move |deps, _: rpc::SubscriptionTaskExecutor| rpc_ext_builder(deps)),
+	)
+	.await
+}
+
+/// Start a parachain node running Aura, serving the RPC extensions built by `rpc_ext_builder`.
+async fn start_aura_node<RuntimeApi, Executor, AuraId: AppKey, RB, RBI>(
+	parachain_config: Configuration,
+	selendra_config: Configuration,
+	id: ParaId,
+	rpc_ext_builder: RB,
+) -> sc_service::error::Result<(
+	TaskManager,
+	Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
+)>
+where
+	RuntimeApi: ConstructRuntimeApi<Block, TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>
+		+ Send
+		+ Sync
+		+ 'static,
+	RuntimeApi::RuntimeApi: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
+		+ sp_api::Metadata<Block>
+		+ sp_session::SessionKeys<Block>
+		+ sp_api::ApiExt<
+			Block,
+			StateBackend = sc_client_api::StateBackendFor<TFullBackend<Block>, Block>,
+		> + sp_offchain::OffchainWorkerApi<Block>
+		+ sp_block_builder::BlockBuilder<Block>
+		+ cumulus_primitives_core::CollectCollationInfo<Block>
+		+ sp_consensus_aura::AuraApi<Block, <<AuraId as AppKey>::Pair as Pair>::Public>
+		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
+		+ frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+	sc_client_api::StateBackendFor<TFullBackend<Block>, Block>: sp_api::StateBackend<BlakeTwo256>,
+	Executor: sc_executor::NativeExecutionDispatch + 'static,
+	<<AuraId as AppKey>::Pair as Pair>::Signature:
+		TryFrom<Vec<u8>> + std::hash::Hash + sp_runtime::traits::Member + Codec,
+	RB: FnOnce(
+		&Configuration,
+		&TaskManager,
+		Arc<TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>>,
+		Arc<TFullBackend<Block>>,
+	) -> Result<RBI, sc_service::Error>,
+	RBI: Fn(
+			rpc::FullDeps<
+				TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				sc_transaction_pool::FullPool<
+					Block,
+					TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<Executor>>,
+				>,
+			>,
+			rpc::SubscriptionTaskExecutor,
+		) -> rpc::RpcExtension
+		+ Send
+		+ 'static,
+{
+	start_node_impl::<RuntimeApi, Executor, _, _, _, _>(
+		parachain_config,
+		selendra_config,
+		id,
+		rpc_ext_builder,
 		indracore_build_import_queue::<_, _, AuraId>,
 		|client,
 		 prometheus_registry,
@@ -745,3 +849,147 @@ where
 	)
 	.await
 }
+
+/// Blocks after which an Ethereum log filter that has not been polled is dropped.
+const FILTER_RETAIN_THRESHOLD: u64 = 100;
+/// Maximum number of Ethereum log filters installed at once.
+const MAX_STORED_FILTERS: usize = 500;
+/// Maximum number of logs returned by a single `eth_getLogs` query.
+const MAX_PAST_LOGS: u32 = 10_000;
+/// Maximum number of blocks served by `eth_feeHistory`.
+const FEE_HISTORY_LIMIT: u64 = 2048;
+/// Number of Ethereum blocks, and of their transaction statuses, kept in the RPC cache.
+const ETH_BLOCK_DATA_CACHE_SIZE: usize = 50;
+
+type IndranetClient = TFullClient<
+	Block,
+	indranet_runtime::RuntimeApi,
+	NativeElseWasmExecutor<IndranetRuntimeExecutor>,
+>;
+type IndranetRpcDeps =
+	rpc::FullDeps<IndranetClient, sc_transaction_pool::FullPool<Block, IndranetClient>>;
+
+/// Path of the Frontier database, under the node's base path.
+fn frontier_database_dir(config: &Configuration) -> PathBuf {
+	config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()))
+		.unwrap_or_else(|| {
+			BasePath::from_project("", "", &Cli::executable_name())
+				.config_dir(config.chain_spec.id())
+		})
+		.join("frontier")
+		.join("db")
+}
+
+/// Open the Frontier database mapping Ethereum blocks and transactions to Substrate ones.
+fn open_frontier_backend(config: &Configuration) -> Result<Arc<fc_db::Backend<Block>>, String> {
+	Ok(Arc::new(fc_db::Backend::<Block>::new(&fc_db::DatabaseSettings {
+		source: fc_db::DatabaseSettingsSrc::RocksDb {
+			path: frontier_database_dir(config),
+			cache_size: 0,
+		},
+	})?))
+}
+
+/// Storage overrides reading each Ethereum storage schema directly, falling back to the
+/// runtime API.
+fn storage_overrides(client: Arc<IndranetClient>) -> Arc<OverrideHandle<Block>> {
+	let mut schemas: BTreeMap<_, Box<dyn StorageOverride<_> + Send + Sync>> = BTreeMap::new();
+	schemas.insert(EthereumStorageSchema::V1, Box::new(SchemaV1Override::new(client.clone())));
+	schemas.insert(EthereumStorageSchema::V2, Box::new(SchemaV2Override::new(client.clone())));
+	schemas.insert(EthereumStorageSchema::V3, Box::new(SchemaV3Override::new(client.clone())));
+
+	Arc::new(OverrideHandle { schemas, fallback: Box::new(RuntimeApiStorageOverride::new(client)) })
+}
+
+/// Start an indranet parachain node, which also serves the Ethereum RPC of its EVM.
+///
+/// The EVM tracing RPC is only served if `evm_tracing` is set.
+pub async fn start_indranet_node(
+	parachain_config: Configuration,
+	selendra_config: Configuration,
+	id: ParaId,
+	evm_tracing: bool,
+) -> sc_service::error::Result<(TaskManager, Arc<IndranetClient>)> {
+	start_aura_node::<indranet_runtime::RuntimeApi, IndranetRuntimeExecutor, AuraId, _, _>(
+		parachain_config,
+		selendra_config,
+		id,
+		|config, task_manager, client, backend| {
+			let frontier_backend = open_frontier_backend(config)?;
+			let overrides = storage_overrides(client.clone());
+			let filter_pool: FilterPool = Arc::new(std::sync::Mutex::new(BTreeMap::new()));
+			let fee_history_cache: FeeHistoryCache =
+				Arc::new(std::sync::Mutex::new(BTreeMap::new()));
+
+			task_manager.spawn_essential_handle().spawn(
+				"frontier-mapping-sync-worker",
+				None,
+				MappingSyncWorker::new(
+					client.import_notification_stream(),
+					Duration::new(6, 0),
+					client.clone(),
+					backend,
+					frontier_backend.clone(),
+					SyncStrategy::Parachain,
+				)
+				.for_each(|()| futures::future::ready(())),
+			);
+			task_manager.spawn_essential_handle().spawn(
+				"frontier-schema-cache-task",
+				None,
+				EthTask::ethereum_schema_cache_task(client.clone(), frontier_backend.clone()),
+			);
+			task_manager.spawn_essential_handle().spawn(
+				"frontier-filter-pool",
+				None,
+				EthTask::filter_pool_task(
+					client.clone(),
+					filter_pool.clone(),
+					FILTER_RETAIN_THRESHOLD,
+				),
+			);
+			task_manager.spawn_essential_handle().spawn(
+				"frontier-fee-history",
+				None,
+				EthTask::fee_history_task(
+					client,
+					overrides.clone(),
+					fee_history_cache.clone(),
+					FEE_HISTORY_LIMIT,
+				),
+			);
+
+			let block_data_cache = Arc::new(EthBlockDataCache::new(
+				ETH_BLOCK_DATA_CACHE_SIZE,
+				ETH_BLOCK_DATA_CACHE_SIZE,
+			));
+
+			let rpc_ext_builder = move |deps: IndranetRpcDeps, subscription_executor| {
+				let eth = rpc::EthDeps {
+					graph: deps.pool.pool().clone(),
+					backend: frontier_backend.clone(),
+					overrides: overrides.clone(),
+					filter_pool: filter_pool.clone(),
+					max_stored_filters: MAX_STORED_FILTERS,
+					max_past_logs: MAX_PAST_LOGS,
+					fee_history_limit: FEE_HISTORY_LIMIT,
+					fee_history_cache: fee_history_cache.clone(),
+					block_data_cache: block_data_cache.clone(),
+					tracing: evm_tracing,
+				};
+
+				rpc::create_full_with_eth::<_, _, _, _, indranet_runtime::IdentityRegistration>(
+					deps,
+					eth,
+					subscription_executor,
+				)
+			};
+
+			Ok(rpc_ext_builder)
+		},
+	)
+	.await
+}
@@ -0,0 +1,150 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # ERC-20 Allowances Pallet
+//!
+//! - [`Config`]
+//!
+//! ## Overview
+//!
+//! Stores the ERC-20 allowances of the token precompiles, for the native currency and for each
+//! asset. It has no extrinsics: the precompiles set allowances through [`Pallet::approve`] and
+//! use them through [`Pallet::spend_allowance`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod tests;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+pub use pallet::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, CheckedSub, Zero},
+	RuntimeDebug,
+};
+
+/// A token with ERC-20 allowances.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Token<AssetId> {
+	/// The native currency.
+	Native,
+	/// An asset.
+	Asset(AssetId),
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The balance of the tokens.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+
+		/// The identifier of an asset.
+		type AssetId: Parameter + Member + Copy + MaxEncodedLen;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An owner allowed a spender to transfer an amount of its tokens.
+		Approved {
+			token: Token<T::AssetId>,
+			owner: T::AccountId,
+			spender: T::AccountId,
+			amount: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The amount exceeds the allowance of the spender.
+		InsufficientAllowance,
+	}
+
+	/// The amount of tokens each spender is allowed to transfer on behalf of each owner.
+	#[pallet::storage]
+	pub type Allowances<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, Token<T::AssetId>>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		T::Balance,
+		ValueQuery,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The amount of `token` that `spender` may transfer on behalf of `owner`.
+	pub fn allowance(
+		token: Token<T::AssetId>,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+	) -> T::Balance {
+		Allowances::<T>::get((token, owner, spender))
+	}
+
+	/// Allow `spender` to transfer `amount` of `token` on behalf of `owner`, replacing the
+	/// previous allowance.
+	pub fn approve(
+		token: Token<T::AssetId>,
+		owner: T::AccountId,
+		spender: T::AccountId,
+		amount: T::Balance,
+	) {
+		if amount.is_zero() {
+			Allowances::<T>::remove((token, &owner, &spender));
+		} else {
+			Allowances::<T>::insert((token, &owner, &spender), amount);
+		}
+
+		Self::deposit_event(Event::Approved { token, owner, spender, amount });
+	}
+
+	/// Use `amount` of the allowance of `spender` over the `token` of `owner`.
+	pub fn spend_allowance(
+		token: Token<T::AssetId>,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Allowances::<T>::try_mutate_exists(
+			(token, owner, spender),
+			|maybe_allowance| -> DispatchResult {
+				let allowance = maybe_allowance
+					.unwrap_or_default()
+					.checked_sub(&amount)
+					.ok_or(Error::<T>::InsufficientAllowance)?;
+				*maybe_allowance = if allowance.is_zero() { None } else { Some(allowance) };
+				Ok(())
+			},
+		)
+	}
+}
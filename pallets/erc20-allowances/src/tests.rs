@@ -0,0 +1,132 @@
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_erc20_allowances;
+
+use frame_support::{assert_noop, assert_ok, parameter_types};
+
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Erc20Allowances: pallet_erc20_allowances::{Pallet, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type PalletInfo = PalletInfo;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_erc20_allowances::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+type TestError = Error<Test>;
+
+#[test]
+fn approve_replaces_allowance() {
+	new_test_ext().execute_with(|| {
+		Erc20Allowances::approve(Token::Native, 1, 2, 10);
+		Erc20Allowances::approve(Token::Native, 1, 2, 4);
+
+		assert_eq!(Erc20Allowances::allowance(Token::Native, &1, &2), 4);
+		System::assert_last_event(
+			pallet_erc20_allowances::Event::Approved {
+				token: Token::Native,
+				owner: 1,
+				spender: 2,
+				amount: 4,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn allowances_are_per_token() {
+	new_test_ext().execute_with(|| {
+		Erc20Allowances::approve(Token::Asset(7), 1, 2, 10);
+
+		assert_eq!(Erc20Allowances::allowance(Token::Asset(7), &1, &2), 10);
+		assert_eq!(Erc20Allowances::allowance(Token::Asset(8), &1, &2), 0);
+		assert_eq!(Erc20Allowances::allowance(Token::Native, &1, &2), 0);
+		assert_eq!(Erc20Allowances::allowance(Token::Asset(7), &2, &1), 0);
+	});
+}
+
+#[test]
+fn spend_allowance_works() {
+	new_test_ext().execute_with(|| {
+		Erc20Allowances::approve(Token::Native, 1, 2, 10);
+
+		assert_ok!(Erc20Allowances::spend_allowance(Token::Native, &1, &2, 6));
+		assert_eq!(Erc20Allowances::allowance(Token::Native, &1, &2), 4);
+
+		assert_ok!(Erc20Allowances::spend_allowance(Token::Native, &1, &2, 4));
+		assert!(!Allowances::<Test>::contains_key((Token::Native, 1, 2)));
+	});
+}
+
+#[test]
+fn spend_allowance_fails_beyond_allowance() {
+	new_test_ext().execute_with(|| {
+		Erc20Allowances::approve(Token::Native, 1, 2, 10);
+
+		assert_noop!(
+			Erc20Allowances::spend_allowance(Token::Native, &1, &2, 11),
+			TestError::InsufficientAllowance
+		);
+		assert_noop!(
+			Erc20Allowances::spend_allowance(Token::Native, &2, &1, 1),
+			TestError::InsufficientAllowance
+		);
+	});
+}
@@ -0,0 +1,236 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # EVM Accounts Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! Binds an EVM address to a Substrate account, so that both sides share a single account and
+//! balance.
+//!
+//! An account claims an address with [`Pallet::claim_account`], passing the `eth_sign`
+//! signature by that address of [`CLAIM_PREFIX`] followed by the chain's genesis hash and the
+//! SCALE encoded account id.
+//! Once bound, [`EvmAddressMapping`] resolves the address to the account and
+//! [`EnsureAddressMapped`] lets the account act as the address in `pallet_evm` calls. Addresses
+//! that are not bound keep resolving through [`Config::FallbackAddressMapping`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod tests;
+pub mod weights;
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::tokens::fungible::{Inspect, Transfer},
+};
+use frame_system::{pallet_prelude::*, RawOrigin};
+pub use pallet::*;
+use pallet_evm::{AddressMapping, EnsureAddressOrigin};
+use sp_core::{ecdsa, H160};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::traits::Zero;
+use sp_std::{marker::PhantomData, prelude::*};
+use weights::WeightInfo;
+
+/// The message an EVM address signs to be bound to an account starts with this prefix, followed
+/// by the genesis hash of the chain, so that it cannot be replayed on another one, and the SCALE
+/// encoded account id.
+pub const CLAIM_PREFIX: &[u8] = b"indra evm:";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency whose balance is moved to the account when an address is bound.
+		type Currency: Transfer<Self::AccountId>;
+
+		/// The mapping of EVM addresses that are not bound to an account.
+		type FallbackAddressMapping: AddressMapping<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An EVM address has been bound to an account.
+		AccountClaimed { account_id: T::AccountId, evm_address: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already bound to an EVM address.
+		AccountAlreadyBound,
+		/// The EVM address is already bound to an account.
+		EvmAddressAlreadyBound,
+		/// No address can be recovered from the signature.
+		BadSignature,
+		/// The signature was not made by the claimed EVM address.
+		InvalidSignature,
+		/// The account is not bound to an EVM address.
+		NotBound,
+	}
+
+	/// The account each bound EVM address resolves to.
+	#[pallet::storage]
+	#[pallet::getter(fn accounts)]
+	pub type Accounts<T: Config> = StorageMap<_, Twox64Concat, H160, T::AccountId, OptionQuery>;
+
+	/// The EVM address bound to each account.
+	#[pallet::storage]
+	#[pallet::getter(fn evm_addresses)]
+	pub type EvmAddresses<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, H160, OptionQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bind `evm_address` to the origin, proven by its `signature` of the origin's
+		/// [`Pallet::claim_message`].
+		///
+		/// The balance the address can transfer through [`Config::FallbackAddressMapping`] is
+		/// moved to the origin; what is locked there, or must stay to keep that account alive
+		/// while it is referenced, is left for [`Pallet::sweep_unbound`]. Once bound, Ethereum transactions from the address use the origin's
+		/// balance and nonce, which is raised to the address' former nonce so that the
+		/// transactions it already sent cannot be replayed.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_account())]
+		pub fn claim_account(
+			origin: OriginFor<T>,
+			evm_address: H160,
+			signature: ecdsa::Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountAlreadyBound);
+			ensure!(!Accounts::<T>::contains_key(evm_address), Error::<T>::EvmAddressAlreadyBound);
+
+			let signer = Self::recover_signer(&signature, &Self::claim_message(&who))
+				.ok_or(Error::<T>::BadSignature)?;
+			ensure!(signer == evm_address, Error::<T>::InvalidSignature);
+
+			let unbound = T::FallbackAddressMapping::into_account_id(evm_address);
+			// Read before the transfer, which may reap the unbound account.
+			let unbound_nonce = frame_system::Pallet::<T>::account_nonce(&unbound);
+			Self::sweep(&unbound, &who)?;
+			frame_system::Account::<T>::mutate(&who, |account| {
+				account.nonce = account.nonce.max(unbound_nonce)
+			});
+
+			Accounts::<T>::insert(evm_address, &who);
+			EvmAddresses::<T>::insert(&who, evm_address);
+
+			Self::deposit_event(Event::AccountClaimed { account_id: who, evm_address });
+			Ok(())
+		}
+
+		/// Move the balance the origin's bound address can now transfer through
+		/// [`Config::FallbackAddressMapping`] to the origin, such as funds [`Pallet::claim_account`]
+		/// left behind that have since been unlocked.
+		#[pallet::weight(<T as Config>::WeightInfo::sweep_unbound())]
+		pub fn sweep_unbound(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let evm_address = EvmAddresses::<T>::get(&who).ok_or(Error::<T>::NotBound)?;
+
+			Self::sweep(&T::FallbackAddressMapping::into_account_id(evm_address), &who)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The `eth_sign` (EIP-191) hash of the message binding an EVM address to `who`.
+	pub fn claim_message(who: &T::AccountId) -> [u8; 32] {
+		let mut payload = CLAIM_PREFIX.to_vec();
+		frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero()).encode_to(&mut payload);
+		who.encode_to(&mut payload);
+
+		let mut message = b"\x19Ethereum Signed Message:\n".to_vec();
+		message.extend_from_slice(&Self::ascii_digits(payload.len()));
+		message.extend_from_slice(&payload);
+		keccak_256(&message)
+	}
+
+	/// Move the balance `unbound` can transfer to `who`.
+	fn sweep(unbound: &T::AccountId, who: &T::AccountId) -> DispatchResult {
+		let balance = T::Currency::reducible_balance(unbound, false);
+		if !balance.is_zero() {
+			T::Currency::transfer(unbound, who, balance, false)?;
+		}
+		Ok(())
+	}
+
+	/// The EVM address that signed `message_hash`, if any.
+	pub fn recover_signer(signature: &ecdsa::Signature, message_hash: &[u8; 32]) -> Option<H160> {
+		secp256k1_ecdsa_recover(&signature.0, message_hash)
+			.map(|public| H160::from_slice(&keccak_256(&public)[12..]))
+			.ok()
+	}
+
+	fn ascii_digits(mut n: usize) -> Vec<u8> {
+		let mut digits = Vec::new();
+		loop {
+			digits.push(b'0' + (n % 10) as u8);
+			n /= 10;
+			if n == 0 {
+				break
+			}
+		}
+		digits.reverse();
+		digits
+	}
+}
+
+/// Resolves bound EVM addresses to their account, and the others through
+/// [`Config::FallbackAddressMapping`].
+pub struct EvmAddressMapping<T>(PhantomData<T>);
+
+impl<T: Config> AddressMapping<T::AccountId> for EvmAddressMapping<T> {
+	fn into_account_id(address: H160) -> T::AccountId {
+		Accounts::<T>::get(address)
+			.unwrap_or_else(|| T::FallbackAddressMapping::into_account_id(address))
+	}
+}
+
+/// Ensure that the origin is the account the EVM address is bound to.
+pub struct EnsureAddressMapped<T>(PhantomData<T>);
+
+impl<T: Config, OuterOrigin> EnsureAddressOrigin<OuterOrigin> for EnsureAddressMapped<T>
+where
+	OuterOrigin: Into<Result<RawOrigin<T::AccountId>, OuterOrigin>> + From<RawOrigin<T::AccountId>>,
+{
+	type Success = T::AccountId;
+
+	fn try_address_origin(
+		address: &H160,
+		origin: OuterOrigin,
+	) -> Result<T::AccountId, OuterOrigin> {
+		origin.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if Accounts::<T>::get(address).as_ref() == Some(&who) => Ok(who),
+			r => Err(OuterOrigin::from(r)),
+		})
+	}
+}
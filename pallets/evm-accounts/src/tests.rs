@@ -0,0 +1,401 @@
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_evm_accounts;
+
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{Currency, ExistenceRequirement::AllowDeath, LockableCurrency, WithdrawReasons},
+};
+
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EvmAccounts: pallet_evm_accounts::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type PalletInfo = PalletInfo;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Maps an EVM address to the account id made of its last 8 bytes.
+pub struct TruncatedAddressMapping;
+
+impl AddressMapping<u64> for TruncatedAddressMapping {
+	fn into_account_id(address: H160) -> u64 {
+		let mut bytes = [0u8; 8];
+		bytes.copy_from_slice(&address[12..]);
+		u64::from_be_bytes(bytes)
+	}
+}
+
+impl pallet_evm_accounts::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type FallbackAddressMapping = TruncatedAddressMapping;
+	type WeightInfo = ();
+}
+
+fn secret_key(seed: &[u8]) -> libsecp256k1::SecretKey {
+	libsecp256k1::SecretKey::parse(&keccak_256(seed)).unwrap()
+}
+
+fn evm_address(secret: &libsecp256k1::SecretKey) -> H160 {
+	let public = libsecp256k1::PublicKey::from_secret_key(secret);
+	H160::from_slice(&keccak_256(&public.serialize()[1..])[12..])
+}
+
+fn claim_signature(secret: &libsecp256k1::SecretKey, who: &u64) -> ecdsa::Signature {
+	let message = libsecp256k1::Message::parse(&EvmAccounts::claim_message(who));
+	let (signature, recovery_id) = libsecp256k1::sign(&message, secret);
+
+	let mut raw = [0u8; 65];
+	raw[..64].copy_from_slice(&signature.serialize());
+	raw[64] = recovery_id.serialize();
+	ecdsa::Signature::from_raw(raw)
+}
+
+fn unbound_account(secret: &libsecp256k1::SecretKey) -> u64 {
+	TruncatedAddressMapping::into_account_id(evm_address(secret))
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (unbound_account(&secret_key(b"Alice")), 50)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+type TestError = Error<Test>;
+
+#[test]
+fn claim_account_works() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let address = evm_address(&alice);
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			address,
+			claim_signature(&alice, &1)
+		));
+
+		assert_eq!(EvmAccounts::accounts(address), Some(1));
+		assert_eq!(EvmAccounts::evm_addresses(1), Some(address));
+		System::assert_last_event(
+			pallet_evm_accounts::Event::AccountClaimed { account_id: 1, evm_address: address }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn claim_account_moves_unbound_balance() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+
+		assert_eq!(Balances::free_balance(1), 150);
+		assert_eq!(Balances::free_balance(unbound_account(&alice)), 0);
+	});
+}
+
+#[test]
+fn claim_account_leaves_locked_balance_to_sweep() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let unbound = unbound_account(&alice);
+		// E.g. a vesting schedule.
+		Balances::set_lock(*b"vesting ", &unbound, 20, WithdrawReasons::all());
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+		assert_eq!(Balances::free_balance(1), 130);
+		assert_eq!(Balances::free_balance(unbound), 20);
+
+		Balances::remove_lock(*b"vesting ", &unbound);
+		assert_ok!(EvmAccounts::sweep_unbound(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 150);
+		assert_eq!(Balances::free_balance(unbound), 0);
+	});
+}
+
+#[test]
+fn claim_account_keeps_a_referenced_account_alive() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let unbound = unbound_account(&alice);
+		// E.g. the account holds `pallet_assets` balances.
+		assert_ok!(System::inc_consumers(&unbound));
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+		assert_eq!(Balances::free_balance(1), 149);
+		assert_eq!(Balances::free_balance(unbound), ExistentialDeposit::get());
+
+		System::dec_consumers(&unbound);
+		assert_ok!(EvmAccounts::sweep_unbound(Origin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 150);
+		assert!(!System::account_exists(&unbound));
+	});
+}
+
+#[test]
+fn sweep_unbound_fails_when_not_bound() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(EvmAccounts::sweep_unbound(Origin::signed(1)), TestError::NotBound);
+	});
+}
+
+#[test]
+fn claim_account_carries_over_the_unbound_nonce() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let bob = secret_key(b"Bob");
+		for _ in 0..5 {
+			System::inc_account_nonce(unbound_account(&alice));
+		}
+		System::inc_account_nonce(2);
+		System::inc_account_nonce(2);
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(2),
+			evm_address(&bob),
+			claim_signature(&bob, &2)
+		));
+
+		assert_eq!(System::account_nonce(1), 5);
+		// The account's own nonce is kept when it is higher.
+		assert_eq!(System::account_nonce(2), 2);
+	});
+}
+
+#[test]
+fn address_mapping_resolves_bound_addresses() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let bob = secret_key(b"Bob");
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+
+		assert_eq!(EvmAddressMapping::<Test>::into_account_id(evm_address(&alice)), 1);
+		assert_eq!(
+			EvmAddressMapping::<Test>::into_account_id(evm_address(&bob)),
+			unbound_account(&bob)
+		);
+	});
+}
+
+#[test]
+fn transfers_from_either_side_reach_the_same_balance() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let address = evm_address(&alice);
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			address,
+			claim_signature(&alice, &1)
+		));
+
+		// A Substrate transfer to the account, and an EVM transfer to its address.
+		assert_ok!(Balances::transfer(Origin::signed(2), 1, 10));
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&2,
+			&EvmAddressMapping::<Test>::into_account_id(address),
+			10,
+			AllowDeath
+		));
+
+		assert_eq!(Balances::free_balance(1), 170);
+	});
+}
+
+#[test]
+fn claim_account_fails_for_signature_of_another_account() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+
+		assert_noop!(
+			EvmAccounts::claim_account(
+				Origin::signed(1),
+				evm_address(&alice),
+				claim_signature(&alice, &2)
+			),
+			TestError::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn claim_account_fails_for_signature_on_another_chain() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let signature = claim_signature(&alice, &1);
+
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+
+		assert_noop!(
+			EvmAccounts::claim_account(Origin::signed(1), evm_address(&alice), signature),
+			TestError::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn claim_account_fails_for_another_address() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let bob = secret_key(b"Bob");
+
+		assert_noop!(
+			EvmAccounts::claim_account(
+				Origin::signed(1),
+				evm_address(&bob),
+				claim_signature(&alice, &1)
+			),
+			TestError::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn claim_account_fails_when_already_bound() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let bob = secret_key(b"Bob");
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			evm_address(&alice),
+			claim_signature(&alice, &1)
+		));
+
+		assert_noop!(
+			EvmAccounts::claim_account(
+				Origin::signed(1),
+				evm_address(&bob),
+				claim_signature(&bob, &1)
+			),
+			TestError::AccountAlreadyBound
+		);
+		assert_noop!(
+			EvmAccounts::claim_account(
+				Origin::signed(2),
+				evm_address(&alice),
+				claim_signature(&alice, &2)
+			),
+			TestError::EvmAddressAlreadyBound
+		);
+	});
+}
+
+#[test]
+fn ensure_address_mapped_only_accepts_the_bound_account() {
+	new_test_ext().execute_with(|| {
+		let alice = secret_key(b"Alice");
+		let address = evm_address(&alice);
+
+		assert!(
+			EnsureAddressMapped::<Test>::try_address_origin(&address, Origin::signed(1)).is_err()
+		);
+
+		assert_ok!(EvmAccounts::claim_account(
+			Origin::signed(1),
+			address,
+			claim_signature(&alice, &1)
+		));
+
+		assert_eq!(
+			EnsureAddressMapped::<Test>::try_address_origin(&address, Origin::signed(1)).ok(),
+			Some(1)
+		);
+		assert!(
+			EnsureAddressMapped::<Test>::try_address_origin(&address, Origin::signed(2)).is_err()
+		);
+		assert!(EnsureAddressMapped::<Test>::try_address_origin(&address, Origin::root()).is_err());
+	});
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Fee Split Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! Stores the shares of transaction fees paid into the treasury and burned, which
+//! `UpdateOrigin` sets with [`Call::set_fee_split`]. The fee handlers read them through
+//! [`TreasuryShareOf`] and [`BurnShareOf`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod tests;
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+pub use pallet::*;
+use sp_runtime::Percent;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may set the fee split.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The share of fees paid into the treasury until one is set.
+		#[pallet::constant]
+		type DefaultTreasuryShare: Get<Percent>;
+
+		/// The share of fees burned until one is set.
+		#[pallet::constant]
+		type DefaultBurnShare: Get<Percent>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event {
+		/// The fee split was set.
+		FeeSplitSet { treasury_share: Percent, burn_share: Percent },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The treasury and burn shares together exceed the whole fee.
+		SharesExceedFee,
+	}
+
+	#[pallet::type_value]
+	pub fn DefaultTreasuryShare<T: Config>() -> Percent {
+		T::DefaultTreasuryShare::get()
+	}
+
+	/// The share of fees paid into the treasury.
+	#[pallet::storage]
+	#[pallet::getter(fn treasury_share)]
+	pub type TreasuryShare<T> = StorageValue<_, Percent, ValueQuery, DefaultTreasuryShare<T>>;
+
+	#[pallet::type_value]
+	pub fn DefaultBurnShare<T: Config>() -> Percent {
+		T::DefaultBurnShare::get()
+	}
+
+	/// The share of fees burned.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_share)]
+	pub type BurnShare<T> = StorageValue<_, Percent, ValueQuery, DefaultBurnShare<T>>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay `treasury_share` of fees into the treasury and burn `burn_share` of them.
+		///
+		/// The dispatch origin must be the `UpdateOrigin`.
+		#[pallet::weight(T::DbWeight::get().writes(2))]
+		pub fn set_fee_split(
+			origin: OriginFor<T>,
+			treasury_share: Percent,
+			burn_share: Percent,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(burn_share <= treasury_share.left_from_one(), Error::<T>::SharesExceedFee);
+
+			TreasuryShare::<T>::put(treasury_share);
+			BurnShare::<T>::put(burn_share);
+
+			Self::deposit_event(Event::FeeSplitSet { treasury_share, burn_share });
+			Ok(())
+		}
+	}
+}
+
+/// The share of fees paid into the treasury, as set in the pallet.
+pub struct TreasuryShareOf<T>(PhantomData<T>);
+impl<T: Config> Get<Percent> for TreasuryShareOf<T> {
+	fn get() -> Percent {
+		Pallet::<T>::treasury_share()
+	}
+}
+
+/// The share of fees burned, as set in the pallet.
+pub struct BurnShareOf<T>(PhantomData<T>);
+impl<T: Config> Get<Percent> for BurnShareOf<T> {
+	fn get() -> Percent {
+		Pallet::<T>::burn_share()
+	}
+}
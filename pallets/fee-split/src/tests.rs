@@ -0,0 +1,125 @@
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_fee_split;
+
+use frame_support::{assert_noop, assert_ok, parameter_types};
+
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		FeeSplit: pallet_fee_split::{Pallet, Call, Storage, Event},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const DefaultTreasuryShare: Percent = Percent::from_percent(20);
+	pub const DefaultBurnShare: Percent = Percent::from_percent(10);
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type PalletInfo = PalletInfo;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_fee_split::Config for Test {
+	type Event = Event;
+	type UpdateOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type DefaultTreasuryShare = DefaultTreasuryShare;
+	type DefaultBurnShare = DefaultBurnShare;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+#[test]
+fn shares_default_until_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TreasuryShareOf::<Test>::get(), Percent::from_percent(20));
+		assert_eq!(BurnShareOf::<Test>::get(), Percent::from_percent(10));
+
+		assert_ok!(FeeSplit::set_fee_split(
+			Origin::root(),
+			Percent::from_percent(30),
+			Percent::from_percent(70)
+		));
+
+		assert_eq!(TreasuryShareOf::<Test>::get(), Percent::from_percent(30));
+		assert_eq!(BurnShareOf::<Test>::get(), Percent::from_percent(70));
+		System::assert_last_event(
+			pallet_fee_split::Event::FeeSplitSet {
+				treasury_share: Percent::from_percent(30),
+				burn_share: Percent::from_percent(70),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_fee_split_requires_update_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_fee_split(
+				Origin::signed(1),
+				Percent::from_percent(0),
+				Percent::from_percent(0)
+			),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn shares_cannot_exceed_fee() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_fee_split(
+				Origin::root(),
+				Percent::from_percent(30),
+				Percent::from_percent(71)
+			),
+			Error::<Test>::SharesExceedFee
+		);
+	});
+}
@@ -0,0 +1,44 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the studentid pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+pub use pallet_studentid::{Judgement, RegistrarIndex};
+
+sp_api::decl_runtime_apis! {
+	/// Lookups into the identities registered with the studentid pallet.
+	pub trait IdentityApi<AccountId, Registration> where
+		AccountId: Codec,
+		Registration: Codec,
+	{
+		/// The identity linked to `who`, if any.
+		fn identity_of(who: AccountId) -> Option<Registration>;
+		/// The account linked to the identity whose email hashes to `email_hash` under
+		/// blake2-256.
+		fn account_of(email_hash: H256) -> Option<AccountId>;
+		/// Whether the identity linked to `who` holds an unexpired grant for `service`.
+		fn has_service_access(who: AccountId, service: Vec<u8>) -> bool;
+		/// The judgements given on the identity linked to `who`, sorted by registrar index.
+		fn judgements_of(who: AccountId) -> Vec<(RegistrarIndex, Judgement)>;
+	}
+}
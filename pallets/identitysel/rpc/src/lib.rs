@@ -0,0 +1,160 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the studentid pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Encode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_studentid_rpc_runtime_api::{Judgement, RegistrarIndex};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_studentid_rpc_runtime_api::IdentityApi as IdentityRuntimeApi;
+
+#[rpc]
+pub trait IdentityApi<BlockHash, AccountId> {
+	/// The identity linked to `who`, if any, as the hex encoded SCALE encoding of the runtime's
+	/// `pallet_studentid::RegistrationOf`, the value type of the pallet's `StudentidOf` storage
+	/// in the runtime metadata.
+	///
+	/// That is the `deposit` as a little endian `u128`, the 32 byte `accountId` of the owner, and
+	/// the `info` fields in declaration order: the compact length prefixed `additional` pairs,
+	/// then `display`, `legal`, `web`, `referalhash`, `email`, `pgp_fingerprint` as an option of
+	/// 20 bytes, `account`, `image` and `passwordhash`. Each `Data` field is a leading byte of `0`
+	/// for none, `1 + n` for `n` raw bytes followed by them, or `34` to `37` for a 32 byte
+	/// BLAKE2-256, SHA2-256, Keccak-256 or SHA3-256 hash followed by it.
+	#[rpc(name = "identity_identityOf")]
+	fn identity_of(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<Bytes>>;
+
+	/// The account linked to the identity whose email hashes to `email_hash` under blake2-256.
+	#[rpc(name = "identity_accountOf")]
+	fn account_of(&self, email_hash: H256, at: Option<BlockHash>) -> Result<Option<AccountId>>;
+
+	/// Whether the identity linked to `who` holds an unexpired grant for `service`.
+	#[rpc(name = "identity_hasServiceAccess")]
+	fn has_service_access(
+		&self,
+		who: AccountId,
+		service: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<bool>;
+
+	/// The judgements given on the identity linked to `who`, sorted by registrar index.
+	#[rpc(name = "identity_judgementsOf")]
+	fn judgements_of(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(RegistrarIndex, Judgement)>>;
+}
+
+/// A struct that implements the [`IdentityApi`].
+pub struct Identity<C, Block, Registration> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, Registration)>,
+}
+
+impl<C, Block, Registration> Identity<C, Block, Registration> {
+	/// Create new `Identity` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(Error::RuntimeError.into()),
+		message: "Unable to query identity.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, Registration> IdentityApi<<Block as BlockT>::Hash, AccountId>
+	for Identity<C, Block, Registration>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: IdentityRuntimeApi<Block, AccountId, Registration>,
+	AccountId: Codec,
+	Registration: Codec + Send + Sync + 'static,
+{
+	fn identity_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.identity_of(&at, who)
+			.map(|identity| identity.map(|identity| identity.encode().into()))
+			.map_err(runtime_error)
+	}
+
+	fn account_of(
+		&self,
+		email_hash: H256,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.account_of(&at, email_hash).map_err(runtime_error)
+	}
+
+	fn has_service_access(
+		&self,
+		who: AccountId,
+		service: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.has_service_access(&at, who, service.to_vec()).map_err(runtime_error)
+	}
+
+	fn judgements_of(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(RegistrarIndex, Judgement)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.judgements_of(&at, who).map_err(runtime_error)
+	}
+}
@@ -23,6 +23,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod extension;
+pub mod migrations;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -31,18 +33,29 @@ use codec::{Decode, Encode, MaxEncodedLen};
 
 use sp_io::hashing::{blake2_256, sha2_256};
 
-use frame_support::traits::{Currency, OnUnbalanced, ReservableCurrency};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	traits::{
+		Contains, Currency, EnsureOrigin, ExistenceRequirement::KeepAlive, Get, OnUnbalanced,
+		ReservableCurrency,
+	},
+	PalletId,
+};
+use frame_system::ensure_signed;
 use sp_runtime::{
-	traits::{AppendZerosInput, Zero},
+	traits::{AccountIdConversion, AppendZerosInput, CheckedDiv, Zero},
 	RuntimeDebug,
 };
 
 use scale_info::TypeInfo;
 use sp_std::prelude::*;
 
+pub use extension::{CheckVerifiedIdentity, ValidityError};
 pub use pallet::*;
 pub use types::{
-	Data, IdentityField, IdentityFields, IdentityInfoSel, RegistrarIndex, RegistrationSel,
+	Data, IdentityField, IdentityFields, IdentityInfoSel, Judgement, RegistrarIndex,
+	RegistrationSel, ServiceGrant,
 };
 
 type BalanceOf<T> =
@@ -114,13 +127,38 @@ pub mod pallet {
 		type MaxTokenid: Get<u32>;
 
 		type MaxUseridentities: Get<u32>;
+
+		/// The maximum number of services a single identity may be granted access to.
+		#[pallet::constant]
+		type MaxServiceGrants: Get<u32>;
+
+		/// The pallet's id, used for deriving the account which pays referral rewards.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The amount paid to a referrer once an identity they referred links a web3 account.
+		#[pallet::constant]
+		type ReferralReward: Get<BalanceOf<Self>>;
+
+		/// The number of blocks in a referral era.
+		#[pallet::constant]
+		type ReferralEraLength: Get<Self::BlockNumber>;
+
+		/// The maximum number of referrals a single referrer is rewarded for in one era.
+		#[pallet::constant]
+		type MaxReferralsPerEra: Get<u32>;
+
+		/// Calls which `CheckVerifiedIdentity` only lets accounts with a verified identity sign.
+		type IdentityRequiredCalls: Contains<<Self as frame_system::Config>::Call>;
+
 		/// What to do with slashed funds.
 		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
 		/// The origin which may forcibly set or remove a name. Root can always do this.
 		type ForceOrigin: EnsureOrigin<Self::Origin>;
 
-		/// The origin which may add or remove registrars. Root can always do this.
+		/// The origin which may add or remove registrars, and which may grant or revoke access to
+		/// any service. Root can always do this.
 		type RegistrarOrigin: EnsureOrigin<Self::Origin>;
 	}
 
@@ -132,8 +170,26 @@ pub mod pallet {
 	pub type TokenInfoOf<T> =
 		TokenInfo<<T as frame_system::Config>::AccountId, Data, TokenMetadataOf<T>>;
 
+	pub type RegistrationOf<T> = RegistrationSel<
+		BalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as Config>::MaxAdditionalFields,
+	>;
+
+	pub type JudgementsOf<T> =
+		BoundedVec<(RegistrarIndex, Judgement), <T as Config>::MaxRegistrars>;
+
+	pub type ServiceGrantsOf<T> = BoundedVec<
+		ServiceGrant<<T as frame_system::Config>::BlockNumber>,
+		<T as Config>::MaxServiceGrants,
+	>;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -171,6 +227,46 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The account that owns each service, and so may grant or revoke access to it.
+	#[pallet::storage]
+	#[pallet::getter(fn service_owner)]
+	pub type ServiceOwners<T: Config> = StorageMap<_, Twox64Concat, Data, T::AccountId>;
+
+	/// The services each identity has been granted access to, keyed by the identity's email.
+	#[pallet::storage]
+	#[pallet::getter(fn service_grants)]
+	pub type ServiceGrants<T: Config> =
+		StorageMap<_, Twox64Concat, Email<T>, ServiceGrantsOf<T>, ValueQuery>;
+
+	/// Referrers of identities which have not yet linked a web3 account, keyed by email.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_referral)]
+	pub type PendingReferrals<T: Config> = StorageMap<_, Twox64Concat, Email<T>, T::AccountId>;
+
+	/// The account which referred each linked identity, keyed by email.
+	#[pallet::storage]
+	#[pallet::getter(fn referrer)]
+	pub type Referrers<T: Config> = StorageMap<_, Twox64Concat, Email<T>, T::AccountId>;
+
+	/// The account linked to each identity, keyed by the blake2-256 hash of its email.
+	#[pallet::storage]
+	#[pallet::getter(fn email_hash_account)]
+	pub type EmailHashAccount<T: Config> = StorageMap<_, Twox64Concat, [u8; 32], T::AccountId>;
+
+	/// Judgements given by registrars on each identity, sorted by registrar index and keyed by
+	/// email.
+	#[pallet::storage]
+	#[pallet::getter(fn judgements)]
+	pub type Judgements<T: Config> =
+		StorageMap<_, Twox64Concat, Email<T>, JudgementsOf<T>, ValueQuery>;
+
+	/// The referral era a referrer was last rewarded in, and the number of rewarded referrals
+	/// in that era. A count from an earlier era is reset by the referrer's next reward.
+	#[pallet::storage]
+	#[pallet::getter(fn referral_count)]
+	pub type ReferralCount<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		IdentityAlreadyClaimed,
@@ -185,6 +281,29 @@ pub mod pallet {
 
 		ServiceAccessFailed,
 
+		/// The service name is longer than 32 bytes.
+		ServiceNameTooLong,
+		/// The sender is neither the service owner nor a registrar.
+		NotServiceOwner,
+		/// Too many services granted to the identity.
+		TooManyServiceGrants,
+		/// The identity has not been granted access to the service.
+		ServiceGrantNotFound,
+		/// The expiry of a grant must be in the future.
+		InvalidExpiry,
+		/// The identity has already been referred by another account.
+		AlreadyReferred,
+		/// The sender is not the account owning the identity.
+		NotIdentityOwner,
+		/// The identity has no pending referral.
+		NoPendingReferral,
+		/// The email is longer than `MaxEmailsize`.
+		EmailTooLong,
+		/// The data is longer than 32 bytes.
+		DataTooLong,
+		/// Too many registrars have given a judgement on the identity.
+		TooManyRegistrars,
+
 		/// Too many subs-accounts.
 		TooManySubAccounts,
 
@@ -199,7 +318,7 @@ pub mod pallet {
 		FeeChanged,
 		/// No identity found.
 		NoIdentity,
-		/// Sticky judgement.
+		/// The registrar index is not below `MaxRegistrars`.
 		InvalidIndex,
 		/// The target is invalid.
 		InvalidTarget,
@@ -210,7 +329,7 @@ pub mod pallet {
 	}
 
 	#[pallet::event]
-	// #[pallet::generate_deposit(pub(super) fn deposit_event)]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A name was set or reset (which will remove all judgements).
 		UserRegistered {
@@ -278,6 +397,44 @@ pub mod pallet {
 		RegistrarAdded {
 			registrar_index: RegistrarIndex,
 		},
+
+		/// The owner of a service was set.
+		ServiceOwnerSet {
+			service: Data,
+			owner: T::AccountId,
+		},
+		/// An identity was granted access to a service.
+		ServiceAccessGranted {
+			who: T::AccountId,
+			service: Data,
+			expire_on: Option<T::BlockNumber>,
+		},
+		/// An identity's access to a service was revoked.
+		ServiceAccessRevoked {
+			who: T::AccountId,
+			service: Data,
+		},
+
+		/// A referral was set for an identity.
+		ReferralSet {
+			referrer: T::AccountId,
+		},
+		/// A pending referral was cleared by the owner of the identity.
+		ReferralCleared {
+			referrer: T::AccountId,
+		},
+		/// A referrer was rewarded for a referred identity linking a web3 account.
+		ReferralRewarded {
+			referrer: T::AccountId,
+			referred: T::AccountId,
+			reward: BalanceOf<T>,
+		},
+		/// A judgement was given by a registrar.
+		JudgementGiven {
+			target: T::AccountId,
+			registrar_index: RegistrarIndex,
+			judgement: Judgement,
+		},
 	}
 
 	#[pallet::call]
@@ -291,33 +448,27 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			ensure!(!StudentidOf::<T>::contains_key(&emailx), Error::<T>::IdentityAlreadyClaimed);
 
 			let add: BoundedVec<_, T::MaxAdditionalFields> = vec![
-				(
-					Data::Raw(b"number".to_vec().try_into().unwrap()),
-					Data::Raw(10u32.encode().try_into().unwrap()),
-				),
-				(
-					Data::Raw(b"text".to_vec().try_into().unwrap()),
-					Data::Raw(b"10".to_vec().try_into().unwrap()),
-				),
+				(Self::raw_data(b"number".to_vec())?, Self::raw_data(10u32.encode())?),
+				(Self::raw_data(b"text".to_vec())?, Self::raw_data(b"10".to_vec())?),
 			]
 			.try_into()
-			.unwrap();
+			.map_err(|_| Error::<T>::TooManyFields)?;
 
 			let info = IdentityInfoSel {
-				display: Data::Raw(b"ten".to_vec().try_into().unwrap()),
-				legal: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap()),
-				image: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap()),
-				web: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap()),
-				referalhash: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap()),
-				email: Data::Raw(email.clone().try_into().unwrap()),
+				display: Self::raw_data(b"ten".to_vec())?,
+				legal: Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?,
+				image: Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?,
+				web: Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?,
+				referalhash: Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?,
+				email: Self::raw_data(email.clone())?,
 				passwordhash: Data::BlakeTwo256(blake2_256(&password.clone())),
 				pgp_fingerprint: None,
-				account: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap()),
+				account: Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?,
 				additional: add,
 			};
 
@@ -336,7 +487,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let _sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
 
@@ -356,7 +507,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
 
@@ -365,7 +516,7 @@ pub mod pallet {
 			let newpassword = Data::BlakeTwo256(blake2_256(&password.clone()));
 
 			info.passwordhash = newpassword;
-			info.account = Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec().try_into().unwrap());
+			info.account = Self::raw_data(b"The Right Ordinal Ten, Esq.".to_vec())?;
 
 			let reg = RegistrationSel { accountId: sender, info, deposit: Zero::zero() };
 
@@ -382,20 +533,28 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
 
+			// An identity can only ever be referred by a single account.
+			ensure!(!<Referrers<T>>::contains_key(&emailx), Error::<T>::AlreadyReferred);
+			if let Some(referrer) = <PendingReferrals<T>>::get(&emailx) {
+				ensure!(referrer == sender, Error::<T>::AlreadyReferred);
+			}
+
 			let hashtoset = Data::Sha256(sha2_256(&referal.clone()));
 
 			let mut info = id.info;
 
 			info.referalhash = hashtoset;
 
-			let reg = RegistrationSel { accountId: sender, info, deposit: Zero::zero() };
+			let reg = RegistrationSel { accountId: id.accountId, info, deposit: Zero::zero() };
 
-			<StudentidOf<T>>::insert(emailx, reg);
+			<StudentidOf<T>>::insert(&emailx, reg);
+			<PendingReferrals<T>>::insert(&emailx, &sender);
 
+			Self::deposit_event(Event::ReferralSet { referrer: sender });
 			Ok(())
 		}
 
@@ -417,7 +576,7 @@ pub mod pallet {
 
 			ensure!(sender == idtolink, Error::<T>::SignerNotmatching);
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
 
@@ -428,11 +587,18 @@ pub mod pallet {
 			ensure!(info.referalhash == hashtocheck, Error::<T>::ReferalFailed);
 
 			// Remove referal
-			info.referalhash = Data::Raw(b"null".to_vec().try_into().unwrap());
+			info.referalhash = Self::raw_data(b"null".to_vec())?;
 
 			let reg = RegistrationSel { accountId: sender.clone(), info, deposit: Zero::zero() };
 
 			<StudentidOf<T>>::insert(emailx.clone(), reg);
+
+			if let Some(referrer) = <PendingReferrals<T>>::take(&emailx) {
+				<Referrers<T>>::insert(&emailx, &referrer);
+				Self::reward_referrer(referrer, &sender);
+			}
+
+			<EmailHashAccount<T>>::insert(blake2_256(&emailx), &sender);
 			<EmailId<T>>::insert(sender, emailx);
 
 			Ok(())
@@ -444,61 +610,305 @@ pub mod pallet {
 
 			let sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email.clone())?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
 			ensure!(sender == id.accountId, Error::<T>::LoginFailed);
 
-			let _tokenid: TokenId<T> = email.clone().try_into().unwrap();
+			let _tokenid: TokenId<T> =
+				email.clone().try_into().map_err(|_| Error::<T>::DataTooLong)?;
 
 			Ok(())
 		}
 
+		/// Grant `idtoaccess` access to `service`, optionally until block `expire_on`.
+		///
+		/// The dispatch origin must be the `RegistrarOrigin` or the owner of `service`. Granting a
+		/// service the identity already holds replaces its expiry.
 		#[pallet::weight(1_000)]
 		pub fn set_accessservice_sel17(
 			origin: OriginFor<T>,
 			idtoaccess: T::AccountId,
 			service: Vec<u8>,
+			expire_on: Option<T::BlockNumber>,
 		) -> DispatchResult {
-			let _sender = ensure_signed(origin)?;
+			let service = Self::service_data(service)?;
+			Self::ensure_service_granter(origin, &service)?;
+
+			if let Some(expire_on) = expire_on {
+				ensure!(
+					expire_on > <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			let email = <EmailId<T>>::get(&idtoaccess).ok_or(Error::<T>::NoIdentity)?;
+			ensure!(<StudentidOf<T>>::contains_key(&email), Error::<T>::NoIdentity);
+
+			<ServiceGrants<T>>::try_mutate(&email, |grants| -> DispatchResult {
+				match grants.iter_mut().find(|grant| grant.service == service) {
+					Some(grant) => grant.expire_on = expire_on,
+					None => grants
+						.try_push(ServiceGrant { service: service.clone(), expire_on })
+						.map_err(|_| Error::<T>::TooManyServiceGrants)?,
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ServiceAccessGranted {
+				who: idtoaccess,
+				service,
+				expire_on,
+			});
+			Ok(())
+		}
 
-			let email = <EmailId<T>>::get(idtoaccess.clone()).ok_or(Error::<T>::NoIdentity)?;
+		/// Check that the sender's identity holds an unexpired grant for `service`.
+		#[pallet::weight(1_000)]
+		pub fn check_web3access_sel18(origin: OriginFor<T>, service: Vec<u8>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
 
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let service = Self::service_data(service)?;
 
-			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
+			ensure!(Self::has_service_access(&sender, &service), Error::<T>::ServiceAccessFailed);
 
-			let web = Data::Raw(service.try_into().unwrap());
+			Ok(())
+		}
 
-			let mut info = id.info;
+		/// Revoke the access of `idtoaccess` to `service`.
+		///
+		/// The dispatch origin must be the `RegistrarOrigin` or the owner of `service`.
+		#[pallet::weight(1_000)]
+		pub fn revoke_accessservice_sel19(
+			origin: OriginFor<T>,
+			idtoaccess: T::AccountId,
+			service: Vec<u8>,
+		) -> DispatchResult {
+			let service = Self::service_data(service)?;
+			Self::ensure_service_granter(origin, &service)?;
+
+			let email = <EmailId<T>>::get(&idtoaccess).ok_or(Error::<T>::NoIdentity)?;
+
+			<ServiceGrants<T>>::try_mutate_exists(&email, |maybe_grants| -> DispatchResult {
+				let grants = maybe_grants.as_mut().ok_or(Error::<T>::ServiceGrantNotFound)?;
+				let pos = grants
+					.iter()
+					.position(|grant| grant.service == service)
+					.ok_or(Error::<T>::ServiceGrantNotFound)?;
+				grants.remove(pos);
+				if grants.is_empty() {
+					*maybe_grants = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ServiceAccessRevoked { who: idtoaccess, service });
+			Ok(())
+		}
 
-			info.web = web;
+		/// Set the account which may grant and revoke access to `service`.
+		///
+		/// The dispatch origin must be the `RegistrarOrigin`.
+		#[pallet::weight(1_000)]
+		pub fn set_serviceowner_sel20(
+			origin: OriginFor<T>,
+			service: Vec<u8>,
+			owner: T::AccountId,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
 
-			let reg = RegistrationSel { accountId: id.accountId, info, deposit: Zero::zero() };
+			let service = Self::service_data(service)?;
 
-			<StudentidOf<T>>::insert(emailx, reg);
+			<ServiceOwners<T>>::insert(&service, &owner);
 
+			Self::deposit_event(Event::ServiceOwnerSet { service, owner });
 			Ok(())
 		}
 
+		/// Provide a judgement on the identity linked to `target` on behalf of registrar
+		/// `registrar_index`, below `MaxRegistrars`, replacing any judgement that registrar gave
+		/// before.
+		///
+		/// The dispatch origin must be the `RegistrarOrigin`.
 		#[pallet::weight(1_000)]
-		pub fn check_web3access_sel18(origin: OriginFor<T>, service: Vec<u8>) -> DispatchResult {
-			// let key = Origin::signed(1);
+		pub fn provide_judgement_sel21(
+			origin: OriginFor<T>,
+			#[pallet::compact] registrar_index: RegistrarIndex,
+			target: T::AccountId,
+			judgement: Judgement,
+		) -> DispatchResult {
+			T::RegistrarOrigin::ensure_origin(origin)?;
+			ensure!(registrar_index < T::MaxRegistrars::get(), Error::<T>::InvalidIndex);
+
+			let email = <EmailId<T>>::get(&target).ok_or(Error::<T>::NoIdentity)?;
+			ensure!(<StudentidOf<T>>::contains_key(&email), Error::<T>::NoIdentity);
+
+			<Judgements<T>>::try_mutate(&email, |judgements| -> DispatchResult {
+				match judgements.binary_search_by_key(&registrar_index, |j| j.0) {
+					Ok(pos) => judgements[pos].1 = judgement,
+					Err(pos) => judgements
+						.try_insert(pos, (registrar_index, judgement))
+						.map_err(|_| Error::<T>::TooManyRegistrars)?,
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::JudgementGiven { target, registrar_index, judgement });
+			Ok(())
+		}
 
+		/// Clear the pending referral of the identity of `email`, along with the referral code
+		/// its referrer set, so that another account may refer it.
+		///
+		/// The dispatch origin must be the account owning the identity.
+		#[pallet::weight(1_000)]
+		pub fn clear_referal_sel22(origin: OriginFor<T>, email: Vec<u8>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			let email = <EmailId<T>>::get(sender.clone()).ok_or(Error::<T>::NoIdentity)?;
-
-			let emailx: BoundedVec<_, T::MaxEmailsize> = email.clone().try_into().unwrap();
+			let emailx = Self::email_of(email)?;
 
 			let id = <StudentidOf<T>>::get(&emailx).ok_or(Error::<T>::NoIdentity)?;
+			ensure!(id.accountId == sender, Error::<T>::NotIdentityOwner);
 
-			let servicetocheck = Data::Raw(service.try_into().unwrap());
-			let info = id.info;
+			let referrer =
+				<PendingReferrals<T>>::take(&emailx).ok_or(Error::<T>::NoPendingReferral)?;
+
+			let mut info = id.info;
+
+			info.referalhash = Self::raw_data(b"null".to_vec())?;
 
-			ensure!(servicetocheck == info.web, Error::<T>::ServiceAccessFailed);
+			let reg = RegistrationSel { accountId: id.accountId, info, deposit: Zero::zero() };
+
+			<StudentidOf<T>>::insert(&emailx, reg);
 
+			Self::deposit_event(Event::ReferralCleared { referrer });
 			Ok(())
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// The account which holds the funds paid out as referral rewards.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Pay the referral reward to `referrer` unless it referred itself or has already been
+	/// rewarded `MaxReferralsPerEra` times in the current era.
+	fn reward_referrer(referrer: T::AccountId, referred: &T::AccountId) {
+		if &referrer == referred {
+			return
+		}
+
+		let era = <frame_system::Pallet<T>>::block_number()
+			.checked_div(&T::ReferralEraLength::get())
+			.unwrap_or_else(Zero::zero);
+		let count = match <ReferralCount<T>>::get(&referrer) {
+			(last_era, count) if last_era == era => count,
+			_ => 0,
+		};
+		if count >= T::MaxReferralsPerEra::get() {
+			return
+		}
+
+		let reward = T::ReferralReward::get();
+		if T::Currency::transfer(&Self::account_id(), &referrer, reward, KeepAlive).is_ok() {
+			<ReferralCount<T>>::insert(&referrer, (era, count + 1));
+			Self::deposit_event(Event::ReferralRewarded {
+				referrer,
+				referred: referred.clone(),
+				reward,
+			});
+		}
+	}
+
+	/// The identity linked to `who`, if any.
+	pub fn identity_of(who: &T::AccountId) -> Option<RegistrationOf<T>> {
+		<EmailId<T>>::get(who).and_then(|email| <StudentidOf<T>>::get(&email))
+	}
+
+	/// The account linked to the identity whose email hashes to `email_hash` under blake2-256.
+	pub fn account_of(email_hash: &[u8; 32]) -> Option<T::AccountId> {
+		<EmailHashAccount<T>>::get(email_hash)
+	}
+
+	/// The judgements given on the identity linked to `who`, sorted by registrar index.
+	pub fn judgements_of(who: &T::AccountId) -> Vec<(RegistrarIndex, Judgement)> {
+		<EmailId<T>>::get(who)
+			.map(|email| <Judgements<T>>::get(&email).into_inner())
+			.unwrap_or_default()
+	}
+
+	/// Whether `who` is linked to an identity that a registrar judged accurate and none judged
+	/// erroneous.
+	pub fn is_verified(who: &T::AccountId) -> bool {
+		let judgements = Self::judgements_of(who);
+		judgements.iter().any(|(_, judgement)| judgement.is_verified()) &&
+			!judgements.iter().any(|(_, judgement)| *judgement == Judgement::Erroneous)
+	}
+
+	/// Whether the identity linked to `who` holds an unexpired grant for `service`.
+	pub fn has_service_access(who: &T::AccountId, service: &Data) -> bool {
+		let now = <frame_system::Pallet<T>>::block_number();
+		<EmailId<T>>::get(who)
+			.map(|email| {
+				<ServiceGrants<T>>::get(&email)
+					.iter()
+					.any(|grant| &grant.service == service && grant.is_active(&now))
+			})
+			.unwrap_or(false)
+	}
+
+	fn email_of(email: Vec<u8>) -> Result<Email<T>, DispatchError> {
+		email.try_into().map_err(|_| Error::<T>::EmailTooLong.into())
+	}
+
+	fn raw_data(data: Vec<u8>) -> Result<Data, DispatchError> {
+		Ok(Data::Raw(data.try_into().map_err(|_| Error::<T>::DataTooLong)?))
+	}
+
+	fn service_data(service: Vec<u8>) -> Result<Data, DispatchError> {
+		Ok(Data::Raw(service.try_into().map_err(|_| Error::<T>::ServiceNameTooLong)?))
+	}
+
+	/// Ensure `origin` is either the `RegistrarOrigin` or the signed owner of `service`.
+	fn ensure_service_granter(origin: T::Origin, service: &Data) -> DispatchResult {
+		if let Err(origin) = T::RegistrarOrigin::try_origin(origin) {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				<ServiceOwners<T>>::get(service).as_ref() == Some(&sender),
+				Error::<T>::NotServiceOwner
+			);
+		}
+		Ok(())
+	}
+}
+
+/// Ensure the origin is signed by an account with a verified identity, yielding the account.
+///
+/// Unlike [`CheckVerifiedIdentity`], this checks the origin a call is dispatched with, and so
+/// the account a proxy, multisig or batch call acts for.
+pub struct EnsureVerifiedIdentity<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> EnsureOrigin<T::Origin> for EnsureVerifiedIdentity<T> {
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::Origin) -> Result<Self::Success, T::Origin> {
+		o.into().and_then(|o| match o {
+			frame_system::RawOrigin::Signed(who) if Pallet::<T>::is_verified(&who) => Ok(who),
+			r => Err(T::Origin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> T::Origin {
+		let who: T::AccountId = frame_benchmarking::account("verified", 0, 0);
+		let email: Email<T> = b"verified@indra.net".to_vec().try_into().unwrap_or_default();
+		<EmailId<T>>::insert(&who, &email);
+		<Judgements<T>>::insert(
+			&email,
+			JudgementsOf::<T>::try_from(vec![(0, Judgement::KnownGood)]).unwrap_or_default(),
+		);
+		frame_system::RawOrigin::Signed(who).into()
+	}
+}
@@ -19,11 +19,14 @@
 
 use super::*;
 use crate as pallet_studentid;
-use frame_support::{assert_noop, assert_ok, ord_parameter_types, parameter_types};
+use frame_support::{
+	assert_noop, assert_ok, ord_parameter_types, parameter_types, weights::DispatchInfo,
+};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup, SignedExtension},
+	transaction_validity::InvalidTransaction,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -98,7 +101,11 @@ parameter_types! {
 	pub const MaxRegistrars: u32 = 20;
 	pub const MaxEmailsize: u32 = 30;
 	pub const MaxTokenid: u32 = 30;
-
+	pub const MaxServiceGrants: u32 = 2;
+	pub const IdentityPalletId: PalletId = PalletId(*b"sel/refr");
+	pub const ReferralReward: u64 = 5;
+	pub const ReferralEraLength: u64 = 10;
+	pub const MaxReferralsPerEra: u32 = 1;
 }
 ord_parameter_types! {
 	pub const One: u64 = 1;
@@ -106,6 +113,13 @@ ord_parameter_types! {
 	pub const MaxAccessTokenMetadata: u32 = 1;
 
 }
+pub struct IdentityRequiredCalls;
+impl Contains<Call> for IdentityRequiredCalls {
+	fn contains(c: &Call) -> bool {
+		matches!(c, Call::Balances(pallet_balances::Call::transfer { .. }))
+	}
+}
+
 //type EnsureOneOrRoot = EnsureOneOf<EnsureRoot<u64>, EnsureSignedBy<One, u64>>;
 //type EnsureTwoOrRoot = EnsureOneOf<EnsureRoot<u64>, EnsureSignedBy<Two, u64>>;
 impl pallet_studentid::Config for Test {
@@ -122,6 +136,12 @@ impl pallet_studentid::Config for Test {
 	type MaxEmailsize = MaxEmailsize;
 	type MaxAccessTokenMetadata = MaxAccessTokenMetadata;
 	type MaxTokenid = MaxTokenid;
+	type MaxServiceGrants = MaxServiceGrants;
+	type PalletId = IdentityPalletId;
+	type ReferralReward = ReferralReward;
+	type ReferralEraLength = ReferralEraLength;
+	type MaxReferralsPerEra = MaxReferralsPerEra;
+	type IdentityRequiredCalls = IdentityRequiredCalls;
 
 	type RegistrarOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
@@ -130,7 +150,15 @@ impl pallet_studentid::Config for Test {
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(1, 10), (2, 10), (3, 10), (10, 100), (20, 100), (30, 100)],
+		balances: vec![
+			(1, 10),
+			(2, 10),
+			(3, 10),
+			(10, 100),
+			(20, 100),
+			(30, 100),
+			(Identity::account_id(), 100),
+		],
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -292,11 +320,451 @@ fn access_web3_method_sela() {
 			Identity::check_web3access_sel18(Origin::signed(staff), service.clone()),
 			Error::<Test>::ServiceAccessFailed
 		);
+		assert_ok!(Identity::set_serviceowner_sel20(Origin::root(), service.clone(), manager));
 		assert_ok!(Identity::set_accessservice_sel17(
 			Origin::signed(manager),
 			staff,
-			service.clone()
+			service.clone(),
+			None
+		));
+		assert_ok!(Identity::check_web3access_sel18(Origin::signed(staff), service.clone()));
+	});
+}
+
+fn register_and_link(who: u64, email: &[u8]) {
+	let pass: Vec<u8> = b"hello123".to_vec();
+	let referal: Vec<u8> = b"referABCD".to_vec();
+	assert_ok!(Identity::request_registration_sel11(Origin::signed(who), email.to_vec(), pass));
+	assert_ok!(Identity::set_referal_sel12(Origin::signed(who), email.to_vec(), referal.clone()));
+	assert_ok!(Identity::create_web3link_sel15(Origin::signed(who), email.to_vec(), who, referal));
+}
+
+#[test]
+fn multiple_service_grants_sela() {
+	new_test_ext().execute_with(|| {
+		let docs: Vec<u8> = b"docsystem".to_vec();
+		let mail: Vec<u8> = b"mailsystem".to_vec();
+		let chat: Vec<u8> = b"chatsystem".to_vec();
+		let staff = 10;
+		register_and_link(staff, b"a@b.com");
+
+		assert_ok!(Identity::set_accessservice_sel17(Origin::root(), staff, docs.clone(), None));
+		assert_ok!(Identity::set_accessservice_sel17(Origin::root(), staff, mail.clone(), None));
+		assert_ok!(Identity::check_web3access_sel18(Origin::signed(staff), docs.clone()));
+		assert_ok!(Identity::check_web3access_sel18(Origin::signed(staff), mail.clone()));
+		assert_noop!(
+			Identity::check_web3access_sel18(Origin::signed(staff), chat.clone()),
+			Error::<Test>::ServiceAccessFailed
+		);
+
+		// Re-granting an existing service does not use up another slot.
+		assert_ok!(Identity::set_accessservice_sel17(Origin::root(), staff, docs.clone(), None));
+		assert_noop!(
+			Identity::set_accessservice_sel17(Origin::root(), staff, chat, None),
+			Error::<Test>::TooManyServiceGrants
+		);
+
+		assert_ok!(Identity::revoke_accessservice_sel19(Origin::root(), staff, docs.clone()));
+		assert_noop!(
+			Identity::check_web3access_sel18(Origin::signed(staff), docs.clone()),
+			Error::<Test>::ServiceAccessFailed
+		);
+		assert_ok!(Identity::check_web3access_sel18(Origin::signed(staff), mail));
+		assert_noop!(
+			Identity::revoke_accessservice_sel19(Origin::root(), staff, docs),
+			Error::<Test>::ServiceGrantNotFound
+		);
+	});
+}
+
+#[test]
+fn service_grant_requires_owner_or_registrar_sela() {
+	new_test_ext().execute_with(|| {
+		let service: Vec<u8> = b"docsystem".to_vec();
+		let owner = 11;
+		let stranger = 12;
+		let staff = 10;
+		register_and_link(staff, b"a@b.com");
+
+		assert_noop!(
+			Identity::set_serviceowner_sel20(Origin::signed(owner), service.clone(), owner),
+			BadOrigin
+		);
+		assert_noop!(
+			Identity::set_accessservice_sel17(Origin::signed(owner), staff, service.clone(), None),
+			Error::<Test>::NotServiceOwner
+		);
+
+		assert_ok!(Identity::set_serviceowner_sel20(Origin::root(), service.clone(), owner));
+		assert_noop!(
+			Identity::set_accessservice_sel17(
+				Origin::signed(stranger),
+				staff,
+				service.clone(),
+				None
+			),
+			Error::<Test>::NotServiceOwner
+		);
+		assert_ok!(Identity::set_accessservice_sel17(
+			Origin::signed(owner),
+			staff,
+			service.clone(),
+			None
+		));
+		assert_noop!(
+			Identity::revoke_accessservice_sel19(Origin::signed(stranger), staff, service.clone()),
+			Error::<Test>::NotServiceOwner
+		);
+		assert_ok!(Identity::revoke_accessservice_sel19(Origin::signed(owner), staff, service));
+	});
+}
+
+#[test]
+fn service_grant_expires_sela() {
+	new_test_ext().execute_with(|| {
+		let service: Vec<u8> = b"docsystem".to_vec();
+		let staff = 10;
+		register_and_link(staff, b"a@b.com");
+		System::set_block_number(5);
+
+		assert_noop!(
+			Identity::set_accessservice_sel17(Origin::root(), staff, service.clone(), Some(5)),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_ok!(Identity::set_accessservice_sel17(
+			Origin::root(),
+			staff,
+			service.clone(),
+			Some(10)
 		));
+
+		System::set_block_number(9);
 		assert_ok!(Identity::check_web3access_sel18(Origin::signed(staff), service.clone()));
+		System::set_block_number(10);
+		assert_noop!(
+			Identity::check_web3access_sel18(Origin::signed(staff), service),
+			Error::<Test>::ServiceAccessFailed
+		);
+	});
+}
+
+fn refer_and_link(referrer: u64, who: u64, email: &[u8]) {
+	let pass: Vec<u8> = b"hello123".to_vec();
+	let referal: Vec<u8> = b"referABCD".to_vec();
+	assert_ok!(Identity::request_registration_sel11(Origin::signed(who), email.to_vec(), pass));
+	assert_ok!(Identity::set_referal_sel12(
+		Origin::signed(referrer),
+		email.to_vec(),
+		referal.clone()
+	));
+	assert_ok!(Identity::create_web3link_sel15(Origin::signed(who), email.to_vec(), who, referal));
+}
+
+#[test]
+fn referral_reward_paid_on_link_sela() {
+	new_test_ext().execute_with(|| {
+		let email: Email<Test> = b"a@b.com".to_vec().try_into().unwrap();
+		refer_and_link(20, 10, b"a@b.com");
+
+		assert_eq!(Identity::referrer(&email), Some(20));
+		assert_eq!(Identity::pending_referral(&email), None);
+		assert_eq!(Balances::free_balance(20), 105);
+		assert_eq!(Balances::free_balance(Identity::account_id()), 95);
+		assert_eq!(Identity::referral_count(20), (0, 1));
+	});
+}
+
+#[test]
+fn referral_reward_capped_per_era_sela() {
+	new_test_ext().execute_with(|| {
+		refer_and_link(20, 10, b"a@b.com");
+		refer_and_link(20, 30, b"c@d.com");
+
+		// The cap is reached, but the referral is still recorded.
+		let email: Email<Test> = b"c@d.com".to_vec().try_into().unwrap();
+		assert_eq!(Identity::referrer(&email), Some(20));
+		assert_eq!(Balances::free_balance(20), 105);
+
+		// A new era resets the cap.
+		System::set_block_number(10);
+		refer_and_link(20, 1, b"e@f.com");
+		assert_eq!(Balances::free_balance(20), 110);
+		assert_eq!(Identity::referral_count(20), (1, 1));
+	});
+}
+
+#[test]
+fn self_referral_not_rewarded_sela() {
+	new_test_ext().execute_with(|| {
+		register_and_link(10, b"a@b.com");
+		assert_eq!(Balances::free_balance(10), 100);
+		assert_eq!(Identity::referral_count(10), (0, 0));
+	});
+}
+
+#[test]
+fn one_referral_per_identity_sela() {
+	new_test_ext().execute_with(|| {
+		let pass: Vec<u8> = b"hello123".to_vec();
+		let referal: Vec<u8> = b"referABCD".to_vec();
+		let user: Vec<u8> = b"a@b.com".to_vec();
+		assert_ok!(Identity::request_registration_sel11(Origin::signed(10), user.clone(), pass));
+		assert_ok!(Identity::set_referal_sel12(Origin::signed(20), user.clone(), referal.clone()));
+		assert_noop!(
+			Identity::set_referal_sel12(Origin::signed(30), user.clone(), referal.clone()),
+			Error::<Test>::AlreadyReferred
+		);
+
+		assert_ok!(Identity::create_web3link_sel15(
+			Origin::signed(10),
+			user.clone(),
+			10,
+			referal.clone()
+		));
+		assert_noop!(
+			Identity::set_referal_sel12(Origin::signed(20), user, referal),
+			Error::<Test>::AlreadyReferred
+		);
+	});
+}
+
+#[test]
+fn owner_clears_pending_referral_sela() {
+	new_test_ext().execute_with(|| {
+		let pass: Vec<u8> = b"hello123".to_vec();
+		let squatted: Vec<u8> = b"squatABCD".to_vec();
+		let referal: Vec<u8> = b"referABCD".to_vec();
+		let user: Vec<u8> = b"a@b.com".to_vec();
+		assert_ok!(Identity::request_registration_sel11(Origin::signed(10), user.clone(), pass));
+		assert_noop!(
+			Identity::clear_referal_sel22(Origin::signed(10), user.clone()),
+			Error::<Test>::NoPendingReferral
+		);
+		assert_ok!(Identity::set_referal_sel12(Origin::signed(30), user.clone(), squatted.clone()));
+
+		assert_noop!(
+			Identity::clear_referal_sel22(Origin::signed(30), user.clone()),
+			Error::<Test>::NotIdentityOwner
+		);
+		assert_ok!(Identity::clear_referal_sel22(Origin::signed(10), user.clone()));
+		assert_eq!(Identity::pending_referral(&Identity::email_of(user.clone()).unwrap()), None);
+		// The code set by the cleared referrer no longer links the identity.
+		assert_noop!(
+			Identity::create_web3link_sel15(Origin::signed(10), user.clone(), 10, squatted),
+			Error::<Test>::ReferalFailed
+		);
+
+		assert_ok!(Identity::set_referal_sel12(Origin::signed(20), user.clone(), referal.clone()));
+		assert_ok!(Identity::create_web3link_sel15(Origin::signed(10), user.clone(), 10, referal));
+		assert_eq!(Identity::referrer(&Identity::email_of(user).unwrap()), Some(20));
+	});
+}
+
+#[test]
+fn email_length_boundary_sela() {
+	new_test_ext().execute_with(|| {
+		let max = MaxEmailsize::get() as usize;
+		let pass: Vec<u8> = b"hello123".to_vec();
+		for len in 1..=max + 2 {
+			let email = vec![b'a'; len];
+			if len <= max {
+				assert_ok!(Identity::request_registration_sel11(
+					Origin::signed(10),
+					email,
+					pass.clone()
+				));
+			} else {
+				assert_noop!(
+					Identity::request_registration_sel11(Origin::signed(10), email, pass.clone()),
+					Error::<Test>::EmailTooLong
+				);
+			}
+		}
+	});
+}
+
+#[test]
+fn overlong_email_rejected_by_every_call_sela() {
+	new_test_ext().execute_with(|| {
+		let email = vec![b'a'; MaxEmailsize::get() as usize + 1];
+		let pass: Vec<u8> = b"hello123".to_vec();
+		assert_noop!(
+			Identity::login_access_sel12(Origin::signed(10), email.clone(), pass.clone()),
+			Error::<Test>::EmailTooLong
+		);
+		assert_noop!(
+			Identity::change_password_sel13(Origin::signed(10), email.clone(), pass.clone()),
+			Error::<Test>::EmailTooLong
+		);
+		assert_noop!(
+			Identity::set_referal_sel12(Origin::signed(10), email.clone(), pass.clone()),
+			Error::<Test>::EmailTooLong
+		);
+		assert_noop!(
+			Identity::create_web3link_sel15(Origin::signed(10), email.clone(), 10, pass),
+			Error::<Test>::EmailTooLong
+		);
+		assert_noop!(
+			Identity::login_web3_sel16(Origin::signed(10), email),
+			Error::<Test>::EmailTooLong
+		);
+	});
+}
+
+#[test]
+fn raw_data_length_boundary_sela() {
+	new_test_ext().execute_with(|| {
+		for len in 0..=34 {
+			let data = vec![b'a'; len];
+			if len <= 32 {
+				assert_eq!(
+					Identity::raw_data(data.clone()),
+					Ok(Data::Raw(data.clone().try_into().unwrap()))
+				);
+				assert_ok!(Identity::set_serviceowner_sel20(Origin::root(), data, 20));
+			} else {
+				assert_eq!(
+					Identity::raw_data(data.clone()),
+					Err(Error::<Test>::DataTooLong.into())
+				);
+				assert_noop!(
+					Identity::set_serviceowner_sel20(Origin::root(), data, 20),
+					Error::<Test>::ServiceNameTooLong
+				);
+			}
+		}
+	});
+}
+
+#[test]
+fn identity_lookups_sela() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Identity::identity_of(&10), None);
+		assert_eq!(Identity::account_of(&blake2_256(b"a@b.com")), None);
+
+		register_and_link(10, b"a@b.com");
+
+		assert_eq!(Identity::identity_of(&10).map(|reg| reg.accountId), Some(10));
+		assert_eq!(Identity::account_of(&blake2_256(b"a@b.com")), Some(10));
+		assert_eq!(Identity::account_of(&blake2_256(b"c@d.com")), None);
+	});
+}
+
+#[test]
+fn provide_judgement_sela() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::provide_judgement_sel21(Origin::root(), 0, 10, Judgement::KnownGood),
+			Error::<Test>::NoIdentity
+		);
+		register_and_link(10, b"a@b.com");
+
+		assert_noop!(
+			Identity::provide_judgement_sel21(Origin::signed(10), 0, 10, Judgement::KnownGood),
+			BadOrigin
+		);
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 3, 10, Judgement::Reasonable));
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 1, 10, Judgement::Erroneous));
+		assert_eq!(
+			Identity::judgements_of(&10),
+			vec![(1, Judgement::Erroneous), (3, Judgement::Reasonable)]
+		);
+
+		// A registrar replaces its own judgement.
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 1, 10, Judgement::KnownGood));
+		assert_eq!(
+			Identity::judgements_of(&10),
+			vec![(1, Judgement::KnownGood), (3, Judgement::Reasonable)]
+		);
+		assert!(Identity::judgements_of(&10).iter().all(|(_, j)| j.is_verified()));
+	});
+}
+
+#[test]
+fn judgement_registrar_index_boundary_sela() {
+	new_test_ext().execute_with(|| {
+		let max = MaxRegistrars::get();
+		register_and_link(10, b"a@b.com");
+
+		assert_noop!(
+			Identity::provide_judgement_sel21(Origin::root(), max, 10, Judgement::KnownGood),
+			Error::<Test>::InvalidIndex
+		);
+		assert_ok!(Identity::provide_judgement_sel21(
+			Origin::root(),
+			max - 1,
+			10,
+			Judgement::KnownGood
+		));
+		assert_eq!(Identity::judgements_of(&10), vec![(max - 1, Judgement::KnownGood)]);
+	});
+}
+
+#[test]
+fn check_verified_identity_sela() {
+	new_test_ext().execute_with(|| {
+		let restricted = Call::Balances(pallet_balances::Call::transfer { dest: 20, value: 1 });
+		let open = Call::System(frame_system::Call::remark { remark: vec![] });
+		let info = DispatchInfo::default();
+		let check = CheckVerifiedIdentity::<Test>::new();
+		let identity_required =
+			InvalidTransaction::Custom(ValidityError::IdentityRequired.into()).into();
+
+		assert_ok!(check.validate(&10, &open, &info, 0));
+		assert_eq!(check.validate(&10, &restricted, &info, 0), Err(identity_required));
+
+		// A linked identity alone is not enough.
+		register_and_link(10, b"a@b.com");
+		assert_eq!(check.validate(&10, &restricted, &info, 0), Err(identity_required));
+
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 0, 10, Judgement::KnownGood));
+		assert_ok!(check.validate(&10, &restricted, &info, 0));
+		assert_ok!(check.clone().pre_dispatch(&10, &restricted, &info, 0));
+
+		// An erroneous judgement from any registrar revokes verification.
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 1, 10, Judgement::Erroneous));
+		assert_eq!(check.validate(&10, &restricted, &info, 0), Err(identity_required));
+	});
+}
+
+#[test]
+fn ensure_verified_identity_sela() {
+	new_test_ext().execute_with(|| {
+		type Ensure = EnsureVerifiedIdentity<Test>;
+
+		assert!(Ensure::try_origin(Origin::root()).is_err());
+		assert!(Ensure::try_origin(Origin::signed(10)).is_err());
+
+		register_and_link(10, b"a@b.com");
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 0, 10, Judgement::KnownGood));
+		assert_eq!(Ensure::try_origin(Origin::signed(10)).ok(), Some(10));
+		assert!(Ensure::try_origin(Origin::signed(20)).is_err());
+
+		assert_ok!(Identity::provide_judgement_sel21(Origin::root(), 1, 10, Judgement::Erroneous));
+		assert_noop!(Ensure::ensure_origin(Origin::signed(10)), BadOrigin);
+	});
+}
+
+#[test]
+fn migrate_to_v1_backfills_email_hash_account_sela() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		register_and_link(10, b"a@b.com");
+		register_and_link(20, b"c@d.com");
+		// Links made before `EmailHashAccount` existed.
+		<EmailHashAccount<Test>>::remove_all(None);
+		StorageVersion::new(0).put::<Identity>();
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), 1);
+		assert_eq!(Identity::account_of(&blake2_256(b"a@b.com")), Some(10));
+		assert_eq!(Identity::account_of(&blake2_256(b"c@d.com")), Some(20));
+
+		// The migration only runs once.
+		<EmailHashAccount<Test>>::remove_all(None);
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Identity::account_of(&blake2_256(b"a@b.com")), None);
 	});
 }
@@ -12,6 +12,8 @@ use scale_info::{
 	build::{Fields, Variants},
 	meta_type, Path, Type, TypeInfo, TypeParameter,
 };
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{traits::Zero, RuntimeDebug};
 use sp_std::{fmt::Debug, iter::once, ops::Add, prelude::*};
 
@@ -163,6 +165,48 @@ impl Default for Data {
 /// An identifier for a single name registrar/identity verification service.
 pub type RegistrarIndex = u32;
 
+/// An attestation of a registrar over how accurate an identity is in describing its owner.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Judgement {
+	/// The default value; no opinion is held.
+	Unknown,
+	/// The data appears to be reasonably acceptable in terms of its accuracy.
+	Reasonable,
+	/// The target is known directly by the registrar and the registrar can fully attest to
+	/// the data's accuracy.
+	KnownGood,
+	/// The data was once good but is currently out of date.
+	OutOfDate,
+	/// The data is imprecise or of sufficiently low-quality to be problematic.
+	LowQuality,
+	/// The data is erroneous. This may be indicative of malicious intent.
+	Erroneous,
+}
+
+impl Judgement {
+	/// Whether this judgement attests to the identity being accurate.
+	pub fn is_verified(&self) -> bool {
+		matches!(self, Judgement::Reasonable | Judgement::KnownGood)
+	}
+}
+
+/// Access to a single service granted to an identity.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ServiceGrant<BlockNumber> {
+	/// The service the identity has been granted access to.
+	pub service: Data,
+	/// The block at which the grant lapses, if any.
+	pub expire_on: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> ServiceGrant<BlockNumber> {
+	/// Whether this grant is still usable at block `now`.
+	pub fn is_active(&self, now: &BlockNumber) -> bool {
+		self.expire_on.as_ref().map_or(true, |expire_on| now < expire_on)
+	}
+}
+
 /// The fields that we use to identify the owner of an account with. Each corresponds to a field
 /// in the `IdentityInfo` struct.
 #[repr(u64)]
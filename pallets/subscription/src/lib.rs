@@ -75,6 +75,18 @@ pub struct Subscription<BlockNumber> {
 	active: bool,
 }
 
+impl<BlockNumber: Copy + PartialOrd> Subscription<BlockNumber> {
+	/// The block the subscription expires on, if it is periodic.
+	pub fn expire_on(&self) -> Option<BlockNumber> {
+		self.expire_on
+	}
+
+	/// Whether the subscription is active and not expired at block `now`.
+	pub fn is_valid_at(&self, now: BlockNumber) -> bool {
+		self.active && self.expire_on.map_or(true, |expire_on| expire_on > now)
+	}
+}
+
 /// The status of a publisher.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum PublisherStatus {
@@ -88,8 +100,12 @@ pub enum PublisherStatus {
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -149,6 +165,9 @@ pub mod pallet {
 		/// The origin which may forcibly unpublish service. Root can always do this.
 		type ForceOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which may publish a service, yielding the publisher.
+		type PublishOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -308,7 +327,7 @@ pub mod pallet {
 			description: Vec<u8>,
 			maybe_periodic: Option<u32>,
 		) -> DispatchResult {
-			let publisher = ensure_signed(origin)?;
+			let publisher = T::PublishOrigin::ensure_origin(origin)?;
 
 			let publisher_status =
 				ApprovedPublisher::<T>::get(&publisher).ok_or(Error::<T>::NotRequestForApproval)?;
@@ -500,3 +519,12 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// Whether `who` holds a valid subscription to `service_id` at the current block.
+	pub fn is_subscribed(service_id: ServiceIndex, who: &T::AccountId) -> bool {
+		Subscriptions::<T>::get(service_id, who).map_or(false, |subscription| {
+			subscription.is_valid_at(<frame_system::Pallet<T>>::block_number())
+		})
+	}
+}
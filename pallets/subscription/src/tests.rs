@@ -92,6 +92,7 @@ impl pallet_subscription::Config for Test {
 	type MaximumDescriptionLength = MaximumDescriptionLength;
 	type ApproveOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type PublishOrigin = frame_system::EnsureSigned<Self::AccountId>;
 	type OnSlash = ();
 	type DataDepositPerByte = DataDepositPerByte;
 	type WeightInfo = pallet_subscription::weights::SubstrateWeight<Test>;
@@ -408,3 +409,31 @@ fn unsubscribe_service_fails() {
 		);
 	});
 }
+
+#[test]
+fn is_subscribed_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Subscriptions::request_approved_publisher(Origin::signed(0)));
+		assert_ok!(Subscriptions::approve_publisher(Origin::root(), 0));
+		let name: Vec<u8> = b"nobody".to_vec().try_into().unwrap();
+		let description: Vec<u8> = b"no desc".to_vec().try_into().unwrap();
+		assert_ok!(Subscriptions::publish_service(
+			Origin::signed(0),
+			10,
+			name.clone(),
+			description.clone(),
+			Some(5)
+		));
+		assert_eq!(Subscriptions::is_subscribed(0, &1), false);
+		assert_ok!(Subscriptions::subscribe_service(Origin::signed(1), 0));
+		assert_eq!(Subscriptions::is_subscribed(0, &1), true);
+		System::set_block_number(6);
+		assert_eq!(Subscriptions::is_subscribed(0, &1), false);
+		assert_ok!(Subscriptions::renew_subscription(Origin::signed(1), 0));
+		assert_eq!(Subscriptions::subscriptions(0, 1).unwrap().expire_on(), Some(11));
+		assert_eq!(Subscriptions::is_subscribed(0, &1), true);
+		assert_ok!(Subscriptions::unsubscribe_service(Origin::signed(1), 0));
+		assert_eq!(Subscriptions::is_subscribed(0, &1), false);
+	});
+}
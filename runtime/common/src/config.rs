@@ -0,0 +1,511 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration shared by the parachain runtimes.
+//!
+//! The macros in this module expand in the runtime that invokes them, so each runtime only declares
+//! what genuinely differs between them: its version, relay chain, governance origins and the
+//! pallets it alone includes.
+
+/// Declares the `currency` and `fee` constants of a runtime, derived from those of the relay chain
+/// whose constants crate is `relay_constants`.
+#[macro_export]
+macro_rules! impl_runtime_constants {
+	(relay_constants = $relay_constants:ident $(,)?) => {
+		pub mod currency {
+			use $crate::Balance;
+			use $relay_constants as constants;
+
+			/// The existential deposit. Set to 1/10 of its parent Relay Chain.
+			pub const EXISTENTIAL_DEPOSIT: Balance = constants::currency::EXISTENTIAL_DEPOSIT / 10;
+
+			pub const UNITS: Balance = constants::currency::UNITS;
+			pub const CENTS: Balance = constants::currency::CENTS;
+			pub const GRAND: Balance = 100 * UNITS;
+			pub const MILLICENTS: Balance = constants::currency::MILLICENTS;
+
+			pub const fn deposit(items: u32, bytes: u32) -> Balance {
+				// map to 1/10 of what the relay chain charges
+				constants::currency::deposit(items, bytes) / 10
+			}
+		}
+
+		/// Fee-related.
+		pub mod fee {
+			use frame_support::weights::{
+				constants::ExtrinsicBaseWeight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+				WeightToFeePolynomial,
+			};
+			use smallvec::smallvec;
+			pub use sp_runtime::Perbill;
+			use $crate::Balance;
+
+			/// The block saturation level. Fees will be updates based on this value.
+			pub const TARGET_BLOCK_FULLNESS: Perbill = Perbill::from_percent(25);
+
+			/// Handles converting a weight scalar to a fee value, based on the scale and
+			/// granularity of the node's balance type.
+			///
+			/// This should typically create a mapping between the following ranges:
+			///   - [0, MAXIMUM_BLOCK_WEIGHT]
+			///   - [Balance::min, Balance::max]
+			///
+			/// Yet, it can be used for any other sort of change to weight-fee. Some examples being:
+			///   - Setting it to `0` will essentially disable the weight fee.
+			///   - Setting it to `1` will cause the literal `#[weight = x]` values to be charged.
+			pub struct WeightToFee;
+			impl WeightToFeePolynomial for WeightToFee {
+				type Balance = Balance;
+				fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+					// in the relay chain, extrinsic base weight (smallest non-zero weight) is
+					// mapped to 1/10 CENT: in the parachains, we map to 1/10 of that, or 1/100 CENT
+					let p = super::currency::CENTS;
+					let q = 100 * Balance::from(ExtrinsicBaseWeight::get());
+					smallvec![WeightToFeeCoefficient {
+						degree: 1,
+						negative: false,
+						coeff_frac: Perbill::from_rational(p % q, q),
+						coeff_integer: p / q,
+					}]
+				}
+			}
+		}
+	};
+}
+
+/// Implements the `Config` of every pallet the parachain runtimes share, together with the
+/// parameters they use.
+///
+/// Must be invoked at the runtime's root, where `construct_runtime!` declares the shared pallets
+/// under their usual names, with the runtime's `VERSION`, `SessionKeys` and `xcm_config` module and
+/// the `currency` constants and `WeightToFee` in scope, and alongside [`impl_proxy_filter`]. The
+/// origins which differ between runtimes are passed in:
+///
+/// ```ignore
+/// parachains_common::impl_shared_pallets! {
+/// 	collator_selection_update_origin = CollatorSelectionUpdateOrigin,
+/// 	treasury_approve_origin = EnsureRoot<AccountId>,
+/// 	treasury_reject_origin = EnsureRoot<AccountId>,
+/// 	fee_split_update_origin = EnsureRoot<AccountId>,
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_shared_pallets {
+	(
+		collator_selection_update_origin = $collator_selection_update_origin:ty,
+		treasury_approve_origin = $treasury_approve_origin:ty,
+		treasury_reject_origin = $treasury_reject_origin:ty,
+		fee_split_update_origin = $fee_split_update_origin:ty $(,)?
+	) => {
+		frame_support::parameter_types! {
+			pub const Version: sp_version::RuntimeVersion = VERSION;
+			pub RuntimeBlockLength: frame_system::limits::BlockLength =
+				frame_system::limits::BlockLength::max_with_normal_ratio(
+					5 * 1024 * 1024,
+					$crate::NORMAL_DISPATCH_RATIO,
+				);
+			pub RuntimeBlockWeights: frame_system::limits::BlockWeights =
+				frame_system::limits::BlockWeights::builder()
+					.base_block(frame_support::weights::constants::BlockExecutionWeight::get())
+					.for_class(frame_support::weights::DispatchClass::all(), |weights| {
+						weights.base_extrinsic =
+							frame_support::weights::constants::ExtrinsicBaseWeight::get();
+					})
+					.for_class(frame_support::weights::DispatchClass::Normal, |weights| {
+						weights.max_total =
+							Some($crate::NORMAL_DISPATCH_RATIO * $crate::MAXIMUM_BLOCK_WEIGHT);
+					})
+					.for_class(frame_support::weights::DispatchClass::Operational, |weights| {
+						weights.max_total = Some($crate::MAXIMUM_BLOCK_WEIGHT);
+						// Operational transactions have some extra reserved space, so that they
+						// are included even if block reached `MAXIMUM_BLOCK_WEIGHT`.
+						weights.reserved = Some(
+							$crate::MAXIMUM_BLOCK_WEIGHT -
+								$crate::NORMAL_DISPATCH_RATIO * $crate::MAXIMUM_BLOCK_WEIGHT
+						);
+					})
+					.avg_block_initialization($crate::AVERAGE_ON_INITIALIZE_RATIO)
+					.build_or_panic();
+			pub const SS58Prefix: u16 = 972;
+		}
+
+		impl frame_system::Config for Runtime {
+			type BaseCallFilter = frame_support::traits::Everything;
+			type BlockWeights = RuntimeBlockWeights;
+			type BlockLength = RuntimeBlockLength;
+			type AccountId = $crate::AccountId;
+			type Call = Call;
+			type Lookup = sp_runtime::traits::AccountIdLookup<$crate::AccountId, ()>;
+			type Index = $crate::Index;
+			type BlockNumber = $crate::BlockNumber;
+			type Hash = $crate::Hash;
+			type Hashing = sp_runtime::traits::BlakeTwo256;
+			type Header = $crate::Header;
+			type Event = Event;
+			type Origin = Origin;
+			type BlockHashCount = selendra_runtime_common::BlockHashCount;
+			type DbWeight = selendra_runtime_common::RocksDbWeight;
+			type Version = Version;
+			type PalletInfo = PalletInfo;
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type AccountData = pallet_balances::AccountData<$crate::Balance>;
+			type SystemWeightInfo = $crate::weights::frame_system::WeightInfo<Runtime>;
+			type SS58Prefix = SS58Prefix;
+			type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
+			type MaxConsumers = frame_support::traits::ConstU32<16>;
+		}
+
+		frame_support::parameter_types! {
+			pub const MinimumPeriod: u64 = $crate::SLOT_DURATION / 2;
+		}
+
+		impl pallet_timestamp::Config for Runtime {
+			/// A timestamp: milliseconds since the unix epoch.
+			type Moment = u64;
+			type OnTimestampSet = ();
+			type MinimumPeriod = MinimumPeriod;
+			type WeightInfo = $crate::weights::pallet_timestamp::WeightInfo<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			pub const UncleGenerations: u32 = 0;
+		}
+
+		impl pallet_authorship::Config for Runtime {
+			type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+			type UncleGenerations = UncleGenerations;
+			type FilterUncle = ();
+			type EventHandler = (CollatorSelection,);
+		}
+
+		frame_support::parameter_types! {
+			pub const ExistentialDeposit: $crate::Balance = EXISTENTIAL_DEPOSIT;
+			pub const MaxLocks: u32 = 50;
+			pub const MaxReserves: u32 = 50;
+		}
+
+		impl pallet_balances::Config for Runtime {
+			type MaxLocks = MaxLocks;
+			/// The type for recording an account's balance.
+			type Balance = $crate::Balance;
+			/// The ubiquitous event type.
+			type Event = Event;
+			type DustRemoval = ();
+			type ExistentialDeposit = ExistentialDeposit;
+			type AccountStore = System;
+			type WeightInfo = $crate::weights::pallet_balances::WeightInfo<Runtime>;
+			type MaxReserves = MaxReserves;
+			type ReserveIdentifier = [u8; 8];
+		}
+
+		frame_support::parameter_types! {
+			/// Relay Chain `TransactionByteFee` / 10
+			pub const TransactionByteFee: $crate::Balance = 1 * MILLICENTS;
+			pub const OperationalFeeMultiplier: u8 = 5;
+			pub const DefaultFeeTreasuryShare: sp_runtime::Percent =
+				sp_runtime::Percent::from_percent(20);
+			pub const DefaultFeeBurnShare: sp_runtime::Percent =
+				sp_runtime::Percent::from_percent(10);
+		}
+
+		impl pallet_fee_split::Config for Runtime {
+			type Event = Event;
+			type UpdateOrigin = $fee_split_update_origin;
+			type DefaultTreasuryShare = DefaultFeeTreasuryShare;
+			type DefaultBurnShare = DefaultFeeBurnShare;
+		}
+
+		/// Share of transaction fees paid into the treasury, set through `FeeSplit`.
+		pub type FeeTreasuryShare = pallet_fee_split::TreasuryShareOf<Runtime>;
+		/// Share of transaction fees burned, capped at what the treasury leaves, set through
+		/// `FeeSplit`.
+		pub type FeeBurnShare = pallet_fee_split::BurnShareOf<Runtime>;
+
+		impl pallet_transaction_payment::Config for Runtime {
+			type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<
+				Balances,
+				$crate::impls::DealWithFees<Runtime, FeeTreasuryShare, FeeBurnShare>,
+			>;
+			type TransactionByteFee = TransactionByteFee;
+			type WeightToFee = WeightToFee;
+			type FeeMultiplierUpdate = selendra_runtime_common::SlowAdjustingFeeUpdate<Self>;
+			type OperationalFeeMultiplier = OperationalFeeMultiplier;
+		}
+
+		impl pallet_asset_tx_payment::Config for Runtime {
+			type Fungibles = Assets;
+			type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+				pallet_assets::BalanceToAssetBalance<
+					Balances,
+					Runtime,
+					sp_runtime::traits::ConvertInto,
+				>,
+				$crate::impls::DealWithAssetFees<Runtime, FeeTreasuryShare, FeeBurnShare>,
+			>;
+		}
+
+		frame_support::parameter_types! {
+			pub const TreasuryPalletId: frame_support::PalletId =
+				frame_support::PalletId(*b"py/trsry");
+			pub const ProposalBond: sp_runtime::Permill = sp_runtime::Permill::from_percent(5);
+			pub const ProposalBondMinimum: $crate::Balance = 100 * UNITS;
+			pub const SpendPeriod: $crate::BlockNumber = 6 * $crate::DAYS;
+			pub const Burn: sp_runtime::Permill = sp_runtime::Permill::from_percent(0);
+			pub const MaxApprovals: u32 = 100;
+		}
+
+		impl pallet_treasury::Config for Runtime {
+			type PalletId = TreasuryPalletId;
+			type Currency = Balances;
+			type ApproveOrigin = $treasury_approve_origin;
+			type RejectOrigin = $treasury_reject_origin;
+			type Event = Event;
+			type OnSlash = Treasury;
+			type ProposalBond = ProposalBond;
+			type ProposalBondMinimum = ProposalBondMinimum;
+			type ProposalBondMaximum = ();
+			type SpendPeriod = SpendPeriod;
+			type Burn = Burn;
+			type BurnDestination = ();
+			type SpendFunds = ();
+			type MaxApprovals = MaxApprovals;
+			type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			pub const MinVestedTransfer: $crate::Balance = 1 * UNITS;
+		}
+
+		impl pallet_vesting::Config for Runtime {
+			type Event = Event;
+			type Currency = Balances;
+			type BlockNumberToBalance = sp_runtime::traits::ConvertInto;
+			type MinVestedTransfer = MinVestedTransfer;
+			type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+			const MAX_VESTING_SCHEDULES: u32 = 28;
+		}
+
+		frame_support::parameter_types! {
+			// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
+			pub const DepositBase: $crate::Balance = deposit(1, 88);
+			// Additional storage item size of 32 bytes.
+			pub const DepositFactor: $crate::Balance = deposit(0, 32);
+			pub const MaxSignatories: u16 = 100;
+		}
+
+		impl pallet_multisig::Config for Runtime {
+			type Event = Event;
+			type Call = Call;
+			type Currency = Balances;
+			type DepositBase = DepositBase;
+			type DepositFactor = DepositFactor;
+			type MaxSignatories = MaxSignatories;
+			type WeightInfo = $crate::weights::pallet_multisig::WeightInfo<Runtime>;
+		}
+
+		impl pallet_utility::Config for Runtime {
+			type Event = Event;
+			type Call = Call;
+			type PalletsOrigin = OriginCaller;
+			type WeightInfo = $crate::weights::pallet_utility::WeightInfo<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			// One storage item; key size 32, value size 8; .
+			pub const ProxyDepositBase: $crate::Balance = deposit(1, 40);
+			// Additional storage item size of 33 bytes.
+			pub const ProxyDepositFactor: $crate::Balance = deposit(0, 33);
+			pub const MaxProxies: u16 = 32;
+			// One storage item; key size 32, value size 16
+			pub const AnnouncementDepositBase: $crate::Balance = deposit(1, 48);
+			pub const AnnouncementDepositFactor: $crate::Balance = deposit(0, 66);
+			pub const MaxPending: u16 = 32;
+		}
+
+		impl pallet_proxy::Config for Runtime {
+			type Event = Event;
+			type Call = Call;
+			type Currency = Balances;
+			type ProxyType = $crate::ProxyType;
+			type ProxyDepositBase = ProxyDepositBase;
+			type ProxyDepositFactor = ProxyDepositFactor;
+			type MaxProxies = MaxProxies;
+			type WeightInfo = $crate::weights::pallet_proxy::WeightInfo<Runtime>;
+			type MaxPending = MaxPending;
+			type CallHasher = sp_runtime::traits::BlakeTwo256;
+			type AnnouncementDepositBase = AnnouncementDepositBase;
+			type AnnouncementDepositFactor = AnnouncementDepositFactor;
+		}
+
+		frame_support::parameter_types! {
+			/// Value = 190 bytes (32+32+32+32+16+16+16+1+4+4+4+1)
+			pub const AssetDeposit: $crate::Balance = deposit(1, 190);
+			/// Value = 32 bytes (16+16)
+			pub const ApprovalDeposit: $crate::Balance = deposit(1, 32);
+			pub const AssetsStringLimit: u32 = 50;
+			/// Key = 32 bytes, Value = 36 bytes (32+1+1+1+1)
+			pub const MetadataDepositBase: $crate::Balance = deposit(1, 68);
+			pub const MetadataDepositPerByte: $crate::Balance = deposit(0, 1);
+		}
+
+		/// We allow root and the Relay Chain council to execute privileged asset operations.
+		pub type AssetsForceOrigin = frame_support::traits::EnsureOneOf<
+			frame_system::EnsureRoot<$crate::AccountId>,
+			pallet_xcm::EnsureXcm<
+				pallet_xcm::IsMajorityOfBody<xcm_config::RelayLocation, ExecutiveBody>,
+			>,
+		>;
+
+		impl pallet_assets::Config for Runtime {
+			type Event = Event;
+			type Balance = $crate::Balance;
+			type AssetId = $crate::AssetId;
+			type Currency = Balances;
+			type ForceOrigin = AssetsForceOrigin;
+			type AssetDeposit = AssetDeposit;
+			type MetadataDepositBase = MetadataDepositBase;
+			type MetadataDepositPerByte = MetadataDepositPerByte;
+			type ApprovalDeposit = ApprovalDeposit;
+			type StringLimit = AssetsStringLimit;
+			type Freezer = ();
+			type Extra = ();
+			type WeightInfo = $crate::weights::pallet_assets::WeightInfo<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			/// Key = 4 bytes, Value = 81 bytes (32+16+16+16+1)
+			pub const ClassDeposit: $crate::Balance = deposit(1, 85);
+			/// Key = 8 bytes, Value = 49 bytes (32+16+1)
+			pub const InstanceDeposit: $crate::Balance = deposit(1, 57);
+			pub const KeyLimit: u32 = 32;
+			pub const ValueLimit: u32 = 64;
+			pub const UniquesMetadataDepositBase: $crate::Balance = deposit(1, 129);
+			pub const AttributeDepositBase: $crate::Balance = deposit(1, 0);
+			pub const DepositPerByte: $crate::Balance = deposit(0, 1);
+			pub const UniquesStringLimit: u32 = 128;
+		}
+
+		impl pallet_uniques::Config for Runtime {
+			type Event = Event;
+			type ClassId = u32;
+			type InstanceId = u32;
+			type Currency = Balances;
+			type ForceOrigin = AssetsForceOrigin;
+			type ClassDeposit = ClassDeposit;
+			type InstanceDeposit = InstanceDeposit;
+			type MetadataDepositBase = UniquesMetadataDepositBase;
+			type AttributeDepositBase = AttributeDepositBase;
+			type DepositPerByte = DepositPerByte;
+			type StringLimit = UniquesStringLimit;
+			type KeyLimit = KeyLimit;
+			type ValueLimit = ValueLimit;
+			type WeightInfo = $crate::weights::pallet_uniques::WeightInfo<Runtime>;
+		}
+
+		frame_support::parameter_types! {
+			pub const ReservedXcmpWeight: frame_support::weights::Weight =
+				$crate::MAXIMUM_BLOCK_WEIGHT / 4;
+			pub const ReservedDmpWeight: frame_support::weights::Weight =
+				$crate::MAXIMUM_BLOCK_WEIGHT / 4;
+		}
+
+		impl cumulus_pallet_parachain_system::Config for Runtime {
+			type Event = Event;
+			type OnSystemEvent = ();
+			type SelfParaId = parachain_info::Pallet<Runtime>;
+			type DmpMessageHandler = DmpQueue;
+			type ReservedDmpWeight = ReservedDmpWeight;
+			type OutboundXcmpMessageSource = XcmpQueue;
+			type XcmpMessageHandler = XcmpQueue;
+			type ReservedXcmpWeight = ReservedXcmpWeight;
+		}
+
+		impl parachain_info::Config for Runtime {}
+
+		impl cumulus_pallet_aura_ext::Config for Runtime {}
+
+		impl cumulus_pallet_xcm::Config for Runtime {
+			type Event = Event;
+			type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+		}
+
+		impl cumulus_pallet_xcmp_queue::Config for Runtime {
+			type Event = Event;
+			type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+			type ChannelInfo = ParachainSystem;
+			type VersionWrapper = SelendraXcm;
+			type ExecuteOverweightOrigin = frame_system::EnsureRoot<$crate::AccountId>;
+		}
+
+		impl cumulus_pallet_dmp_queue::Config for Runtime {
+			type Event = Event;
+			type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+			type ExecuteOverweightOrigin = frame_system::EnsureRoot<$crate::AccountId>;
+		}
+
+		frame_support::parameter_types! {
+			pub const Period: u32 = 6 * $crate::HOURS;
+			pub const Offset: u32 = 0;
+			pub const MaxAuthorities: u32 = 100_000;
+		}
+
+		impl pallet_session::Config for Runtime {
+			type Event = Event;
+			type ValidatorId = <Self as frame_system::Config>::AccountId;
+			// we don't have stash and controller, thus we don't need the convert as well.
+			type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+			type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+			type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+			type SessionManager = CollatorSelection;
+			// Essentially just Aura, but lets be pedantic.
+			type SessionHandler =
+				<SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
+			type Keys = SessionKeys;
+			type WeightInfo = $crate::weights::pallet_session::WeightInfo<Runtime>;
+		}
+
+		impl pallet_aura::Config for Runtime {
+			type AuthorityId = $crate::AuraId;
+			type DisabledValidators = ();
+			type MaxAuthorities = MaxAuthorities;
+		}
+
+		frame_support::parameter_types! {
+			pub const PotId: frame_support::PalletId = frame_support::PalletId(*b"PotStake");
+			pub const MaxCandidates: u32 = 1000;
+			pub const MinCandidates: u32 = 5;
+			pub const SessionLength: $crate::BlockNumber = 6 * $crate::HOURS;
+			pub const MaxInvulnerables: u32 = 100;
+			pub const ExecutiveBody: xcm::latest::BodyId = xcm::latest::BodyId::Executive;
+		}
+
+		impl pallet_collator_selection::Config for Runtime {
+			type Event = Event;
+			type Currency = Balances;
+			type UpdateOrigin = $collator_selection_update_origin;
+			type PotId = PotId;
+			type MaxCandidates = MaxCandidates;
+			type MinCandidates = MinCandidates;
+			type MaxInvulnerables = MaxInvulnerables;
+			// should be a multiple of session or things will get inconsistent
+			type KickThreshold = Period;
+			type ValidatorId = <Self as frame_system::Config>::AccountId;
+			type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+			type ValidatorRegistration = Session;
+			type WeightInfo = $crate::weights::pallet_collator_selection::WeightInfo<Runtime>;
+		}
+	};
+}
@@ -16,12 +16,24 @@
 //! Auxillary struct/enums for parachain runtimes.
 //! Taken from selendra/runtime/common (at a21cd64) and adapted for parachains.
 
-use frame_support::traits::{
-	fungibles::{self, Balanced, CreditOf},
-	Contains, Currency, Get, Imbalance, OnUnbalanced,
+use codec::{Compact, Decode, Encode};
+use frame_support::{
+	traits::{
+		fungibles::{self, Balanced, CreditOf},
+		tokens::BalanceConversion,
+		Contains, Currency, Get, GetStorageVersion, Imbalance, OnRuntimeUpgrade, OnUnbalanced,
+		PalletInfoAccess, StorageVersion,
+	},
+	weights::{DispatchInfo, GetDispatchInfo, Weight},
+};
+use pallet_asset_tx_payment::{ChargeAssetTxPayment, HandleCredit};
+use pallet_transaction_payment::{
+	FeeDetails, InclusionFee, OnChargeTransaction, RuntimeDispatchInfo,
+};
+use sp_runtime::{
+	traits::{ConvertInto, Dispatchable, Extrinsic, Zero},
+	PerThing, Percent,
 };
-use pallet_asset_tx_payment::HandleCredit;
-use sp_runtime::traits::Zero;
 use sp_std::marker::PhantomData;
 use xcm::latest::{AssetId, Fungibility::Fungible, MultiAsset, MultiLocation};
 use xcm_executor::traits::FilterAssetLocation;
@@ -49,39 +61,77 @@ where
 	}
 }
 
-/// Implementation of `OnUnbalanced` that deals with the fees by combining tip and fee and passing
-/// the result on to `ToStakingPot`.
-pub struct DealWithFees<R>(PhantomData<R>);
-impl<R> OnUnbalanced<NegativeImbalance<R>> for DealWithFees<R>
+/// Implementation of `OnUnbalanced` that deals with the fees by sending `TreasuryShare` of the fee
+/// to the treasury and burning `BurnShare` of it. The rest of the fee and the whole tip are passed
+/// on to `ToStakingPot`.
+///
+/// The burn is capped at what is left after the treasury's share, so the shares never take more
+/// than the fee.
+pub struct DealWithFees<R, TreasuryShare, BurnShare>(PhantomData<(R, TreasuryShare, BurnShare)>);
+impl<R, TreasuryShare, BurnShare> OnUnbalanced<NegativeImbalance<R>>
+	for DealWithFees<R, TreasuryShare, BurnShare>
 where
-	R: pallet_balances::Config + pallet_collator_selection::Config,
+	R: pallet_balances::Config + pallet_collator_selection::Config + pallet_treasury::Config,
+	pallet_treasury::Pallet<R>: OnUnbalanced<NegativeImbalance<R>>,
+	TreasuryShare: Get<Percent>,
+	BurnShare: Get<Percent>,
 	AccountIdOf<R>:
 		From<selendra_primitives::v1::AccountId> + Into<selendra_primitives::v1::AccountId>,
 	<R as frame_system::Config>::Event: From<pallet_balances::Event<R>>,
 {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance<R>>) {
-		if let Some(mut fees) = fees_then_tips.next() {
+		if let Some(fees) = fees_then_tips.next() {
+			let treasury_share = TreasuryShare::get();
+			let burn_share = BurnShare::get().min(treasury_share.left_from_one());
+			let total = fees.peek();
+
+			let (to_treasury, rest) = fees.split(treasury_share.mul_floor(total));
+			// Dropping the burnt imbalance reduces the total issuance.
+			let (_burnt, mut to_pot) = rest.split(burn_share.mul_floor(total));
 			if let Some(tips) = fees_then_tips.next() {
-				tips.merge_into(&mut fees);
+				tips.merge_into(&mut to_pot);
 			}
-			<ToStakingPot<R> as OnUnbalanced<_>>::on_unbalanced(fees);
+
+			<pallet_treasury::Pallet<R> as OnUnbalanced<_>>::on_unbalanced(to_treasury);
+			<ToStakingPot<R> as OnUnbalanced<_>>::on_unbalanced(to_pot);
 		}
 	}
 }
 
-/// A `HandleCredit` implementation that naively transfers the fees to the block author.
-/// Will drop and burn the assets in case the transfer fails.
-pub struct AssetsToBlockAuthor<R>(PhantomData<R>);
-impl<R> HandleCredit<AccountIdOf<R>, pallet_assets::Pallet<R>> for AssetsToBlockAuthor<R>
+/// A `HandleCredit` implementation that splits fees paid in assets like [`DealWithFees`] does,
+/// sending `TreasuryShare` of them to the treasury, burning `BurnShare` of them and transferring
+/// the rest, tip included, to the block author.
+///
+/// A share which cannot be transferred, such as one below the asset's minimum balance, is
+/// dropped and so burned.
+pub struct DealWithAssetFees<R, TreasuryShare, BurnShare>(
+	PhantomData<(R, TreasuryShare, BurnShare)>,
+);
+impl<R, TreasuryShare, BurnShare> HandleCredit<AccountIdOf<R>, pallet_assets::Pallet<R>>
+	for DealWithAssetFees<R, TreasuryShare, BurnShare>
 where
-	R: pallet_authorship::Config + pallet_assets::Config,
+	R: pallet_authorship::Config + pallet_assets::Config + pallet_treasury::Config,
+	TreasuryShare: Get<Percent>,
+	BurnShare: Get<Percent>,
 	AccountIdOf<R>:
 		From<selendra_primitives::v1::AccountId> + Into<selendra_primitives::v1::AccountId>,
 {
 	fn handle_credit(credit: CreditOf<AccountIdOf<R>, pallet_assets::Pallet<R>>) {
+		let treasury_share = TreasuryShare::get();
+		let burn_share = BurnShare::get().min(treasury_share.left_from_one());
+		let total = credit.peek();
+
+		let (to_treasury, rest) = credit.split(treasury_share.mul_floor(total));
+		// Dropping the burnt credit reduces the asset's total issuance.
+		let (_burnt, to_author) = rest.split(burn_share.mul_floor(total));
+
+		// In case of error: Will drop the result triggering the `OnDrop` of the imbalance.
+		let _ = pallet_assets::Pallet::<R>::resolve(
+			&pallet_treasury::Pallet::<R>::account_id(),
+			to_treasury,
+		);
 		if let Some(author) = pallet_authorship::Pallet::<R>::author() {
-			// In case of error: Will drop the result triggering the `OnDrop` of the imbalance.
-			let _ = pallet_assets::Pallet::<R>::resolve(&author, credit);
+			let _ = pallet_assets::Pallet::<R>::resolve(&author, to_author);
 		}
 	}
 }
@@ -109,11 +159,115 @@ impl<T: Get<MultiLocation>> FilterAssetLocation for AssetsFrom<T> {
 	}
 }
 
+/// Migration that sets the on-chain storage version of a pallet `P` added to an already running
+/// chain. Pallets added by a runtime upgrade never run their genesis build, so their storage
+/// version would otherwise stay at zero and trip later migrations.
+///
+/// Only use this for pallets without on-chain data; it does not migrate any storage items.
+pub struct InitStorageVersion<R, P>(PhantomData<(R, P)>);
+impl<R, P> OnRuntimeUpgrade for InitStorageVersion<R, P>
+where
+	R: frame_system::Config,
+	P: GetStorageVersion + PalletInfoAccess,
+{
+	fn on_runtime_upgrade() -> Weight {
+		if P::on_chain_storage_version() == StorageVersion::new(0) {
+			P::current_storage_version().put::<P>();
+			R::DbWeight::get().reads_writes(1, 1)
+		} else {
+			R::DbWeight::get().reads(1)
+		}
+	}
+}
+
+/// The asset a transaction pays its fees in, read from its `ChargeAssetTxPayment` extension.
+///
+/// Taking the extension by type ties callers to the position of `ChargeAssetTxPayment` in their
+/// `SignedExtra`, so reordering the extensions fails to compile rather than breaking fee quotes.
+pub fn fee_asset<R>(charge: &ChargeAssetTxPayment<R>) -> Option<crate::AssetId>
+where
+	R: pallet_asset_tx_payment::Config,
+	ChargeAssetTxPayment<R>: Encode,
+{
+	// `ChargeAssetTxPayment` does not expose its fields, so read them back from its encoding.
+	let (_tip, asset_id) =
+		<(Compact<crate::Balance>, Option<crate::AssetId>)>::decode(&mut &charge.encode()[..])
+			.ok()?;
+	asset_id
+}
+
+type ToAssetBalance<R> =
+	pallet_assets::BalanceToAssetBalance<pallet_balances::Pallet<R>, R, ConvertInto>;
+
+/// `TransactionPaymentApi` queries that quote fees in the asset a transaction pays them in.
+///
+/// `pallet_transaction_payment` always quotes the native currency, which is not what a
+/// transaction paying through `pallet_asset_tx_payment` is charged. Fees of a transaction naming
+/// an asset that cannot pay them are quoted natively, as if it named none; such a transaction is
+/// rejected by validation anyway.
+pub struct AssetTxPaymentApi<R>(PhantomData<R>);
+impl<R> AssetTxPaymentApi<R>
+where
+	R: pallet_transaction_payment::Config
+		+ pallet_balances::Config<Balance = crate::Balance>
+		+ pallet_assets::Config<AssetId = crate::AssetId, Balance = crate::Balance>,
+	<R as pallet_transaction_payment::Config>::OnChargeTransaction:
+		OnChargeTransaction<R, Balance = crate::Balance>,
+	<R as frame_system::Config>::Call: Dispatchable<Info = DispatchInfo>,
+{
+	/// `pallet_transaction_payment::Pallet::query_info`, with the partial fee in `asset_id`.
+	pub fn query_info<E: Extrinsic + GetDispatchInfo>(
+		uxt: E,
+		len: u32,
+		asset_id: Option<crate::AssetId>,
+	) -> RuntimeDispatchInfo<crate::Balance> {
+		let mut info = pallet_transaction_payment::Pallet::<R>::query_info(uxt, len);
+		if let Some(fee) = asset_id.and_then(|id| Self::fee_in_asset(info.partial_fee, id)) {
+			info.partial_fee = fee;
+		}
+		info
+	}
+
+	/// `pallet_transaction_payment::Pallet::query_fee_details`, with every fee in `asset_id`.
+	pub fn query_fee_details<E: Extrinsic + GetDispatchInfo>(
+		uxt: E,
+		len: u32,
+		asset_id: Option<crate::AssetId>,
+	) -> FeeDetails<crate::Balance> {
+		let details = pallet_transaction_payment::Pallet::<R>::query_fee_details(uxt, len);
+		asset_id
+			.and_then(|id| Self::fee_details_in_asset(&details, id))
+			.unwrap_or(details)
+	}
+
+	/// Convert a native fee into the amount of `asset_id` charged in its place, or `None` if
+	/// `asset_id` cannot pay fees.
+	pub fn fee_in_asset(fee: crate::Balance, asset_id: crate::AssetId) -> Option<crate::Balance> {
+		ToAssetBalance::<R>::to_asset_balance(fee, asset_id).ok()
+	}
+
+	fn fee_details_in_asset(
+		details: &FeeDetails<crate::Balance>,
+		asset_id: crate::AssetId,
+	) -> Option<FeeDetails<crate::Balance>> {
+		let in_asset = |fee| Self::fee_in_asset(fee, asset_id);
+		let inclusion_fee = match &details.inclusion_fee {
+			Some(fee) => Some(InclusionFee {
+				base_fee: in_asset(fee.base_fee)?,
+				len_fee: in_asset(fee.len_fee)?,
+				adjusted_weight_fee: in_asset(fee.adjusted_weight_fee)?,
+			}),
+			None => None,
+		};
+		Some(FeeDetails { inclusion_fee, tip: in_asset(details.tip)? })
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use frame_support::{
-		parameter_types,
+		assert_ok, parameter_types,
 		traits::{FindAuthor, ValidatorRegistration},
 		PalletId,
 	};
@@ -124,7 +278,7 @@ mod tests {
 	use sp_runtime::{
 		testing::Header,
 		traits::{BlakeTwo256, IdentityLookup},
-		Perbill,
+		Perbill, Permill,
 	};
 	use xcm::prelude::*;
 
@@ -141,6 +295,9 @@ mod tests {
 			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 			CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>},
+			Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
+			Authorship: pallet_authorship::{Pallet, Storage},
+			Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 		}
 	);
 
@@ -229,6 +386,37 @@ mod tests {
 		type WeightInfo = ();
 	}
 
+	parameter_types! {
+		pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+		pub const ProposalBond: Permill = Permill::from_percent(5);
+		pub const SpendPeriod: u64 = 2;
+		pub const MaxApprovals: u32 = 100;
+	}
+
+	impl pallet_treasury::Config for Test {
+		type PalletId = TreasuryPalletId;
+		type Currency = Balances;
+		type ApproveOrigin = EnsureRoot<AccountId>;
+		type RejectOrigin = EnsureRoot<AccountId>;
+		type Event = Event;
+		type OnSlash = ();
+		type ProposalBond = ProposalBond;
+		type ProposalBondMinimum = ();
+		type ProposalBondMaximum = ();
+		type SpendPeriod = SpendPeriod;
+		type Burn = ();
+		type BurnDestination = ();
+		type SpendFunds = ();
+		type MaxApprovals = MaxApprovals;
+		type WeightInfo = ();
+	}
+
+	parameter_types! {
+		pub const TreasuryShare: Percent = Percent::from_percent(20);
+		pub const BurnShare: Percent = Percent::from_percent(10);
+		pub const GreedyBurnShare: Percent = Percent::from_percent(90);
+	}
+
 	impl pallet_authorship::Config for Test {
 		type FindAuthor = OneAuthor;
 		type UncleGenerations = ();
@@ -236,6 +424,22 @@ mod tests {
 		type EventHandler = ();
 	}
 
+	impl pallet_assets::Config for Test {
+		type Event = Event;
+		type Balance = u64;
+		type AssetId = u32;
+		type Currency = Balances;
+		type ForceOrigin = EnsureRoot<AccountId>;
+		type AssetDeposit = ();
+		type MetadataDepositBase = ();
+		type MetadataDepositPerByte = ();
+		type ApprovalDeposit = ();
+		type StringLimit = frame_support::traits::ConstU32<50>;
+		type Freezer = ();
+		type Extra = ();
+		type WeightInfo = ();
+	}
+
 	pub fn new_test_ext() -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 		// We use default for brevity, but you can configure as desired if needed.
@@ -253,13 +457,65 @@ mod tests {
 
 			assert_eq!(Balances::free_balance(TEST_ACCOUNT), 0);
 
-			DealWithFees::on_unbalanceds(vec![fee, tip].into_iter());
+			DealWithFees::<Test, (), ()>::on_unbalanceds(vec![fee, tip].into_iter());
 
 			// Author gets 100% of tip and 100% of fee = 30
 			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 30);
 		});
 	}
 
+	#[test]
+	fn test_fees_split_between_treasury_burn_and_pot() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+			let tip = Balances::issue(20);
+			assert_eq!(Balances::total_issuance(), 120);
+
+			DealWithFees::<Test, TreasuryShare, BurnShare>::on_unbalanceds(
+				vec![fee, tip].into_iter(),
+			);
+
+			// Treasury gets 20% of the fee, 10% of it is burned and the pot gets the rest plus
+			// the whole tip.
+			assert_eq!(Balances::free_balance(Treasury::account_id()), 20);
+			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 90);
+			assert_eq!(Balances::total_issuance(), 110);
+		});
+	}
+
+	#[test]
+	fn test_burn_share_capped_by_treasury_share() {
+		new_test_ext().execute_with(|| {
+			let fee = Balances::issue(100);
+			let tip = Balances::issue(20);
+
+			DealWithFees::<Test, TreasuryShare, GreedyBurnShare>::on_unbalanceds(
+				vec![fee, tip].into_iter(),
+			);
+
+			// Only the 80% left after the treasury's share can be burned; the pot keeps the tip.
+			assert_eq!(Balances::free_balance(Treasury::account_id()), 20);
+			assert_eq!(Balances::free_balance(CollatorSelection::account_id()), 20);
+			assert_eq!(Balances::total_issuance(), 40);
+		});
+	}
+
+	#[test]
+	fn test_asset_fees_split_between_treasury_burn_and_author() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::force_create(Origin::root(), 1, TEST_ACCOUNT, true, 1));
+			let credit = <Assets as Balanced<AccountId>>::issue(1, 100);
+			assert_eq!(Assets::total_supply(1), 100);
+
+			DealWithAssetFees::<Test, TreasuryShare, BurnShare>::handle_credit(credit);
+
+			// Treasury gets 20% of the fee, 10% of it is burned and the author gets the rest.
+			assert_eq!(Assets::balance(1, Treasury::account_id()), 20);
+			assert_eq!(Assets::balance(1, TEST_ACCOUNT), 70);
+			assert_eq!(Assets::total_supply(1), 90);
+		});
+	}
+
 	#[test]
 	fn assets_from_filters_correctly() {
 		parameter_types! {
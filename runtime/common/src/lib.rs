@@ -15,9 +15,14 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod config;
 pub mod impls;
+pub mod proxy;
+pub mod weights;
+pub mod xcm_config;
 pub use constants::*;
 pub use opaque::*;
+pub use proxy::ProxyType;
 pub use types::*;
 /// Common types of parachains.
 mod types {
@@ -0,0 +1,144 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The proxy types shared by the parachain runtimes.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use sp_runtime::RuntimeDebug;
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Fully permissioned proxy. Can execute any call on behalf of _proxied_.
+	Any,
+	/// Can execute any call that does not move funds or assets, whether locally or over XCM. May
+	/// still unlock vested funds with `vest`.
+	NonTransfer,
+	/// Proxy with the ability to reject time-delay proxy announcements.
+	CancelProxy,
+	/// Collator selection proxy. Can execute calls related to collator selection mechanism.
+	Collator,
+	/// Assets proxy. Can execute any call from `assets` and `uniques`, including asset transfers.
+	Assets,
+	/// Subscription proxy. Can publish, subscribe to and manage services, including paying for
+	/// subscriptions.
+	Subscription,
+	/// Identity proxy. Can execute any call from `identitysel`.
+	Identity,
+	/// Governance proxy. Can execute the calls of the runtime's governance pallets.
+	Governance,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+/// Implements `InstanceFilter<Call>` for [`ProxyType`] over the runtime's `Call`.
+///
+/// Must be invoked at the runtime's root, where `Call` holds the `Balances`, `Vesting`, `Assets`,
+/// `Uniques`, `SelendraXcm`, `CollatorSelection`, `IndraIdentity`, `Subscription`, `Utility`,
+/// `Multisig` and `Proxy` pallets. The calls a `Governance` proxy may make differ between runtimes,
+/// so they are passed in, as are any value-moving calls of pallets only some runtimes include,
+/// which a `NonTransfer` proxy may not make:
+///
+/// ```ignore
+/// parachains_common::impl_proxy_filter! {
+/// 	governance_calls = Call::Treasury { .. },
+/// 	transfer_calls = Call::EVM { .. },
+/// }
+/// ```
+#[macro_export]
+macro_rules! impl_proxy_filter {
+	(
+		governance_calls = $($governance_calls:pat_param)|+
+		$(, transfer_calls = $($transfer_calls:pat_param)|+)? $(,)?
+	) => {
+		impl frame_support::traits::InstanceFilter<Call> for $crate::ProxyType {
+			fn filter(&self, c: &Call) -> bool {
+				match self {
+					$crate::ProxyType::Any => true,
+					$crate::ProxyType::NonTransfer => !matches!(
+						c,
+						Call::Balances { .. } |
+							Call::Assets { .. } | Call::Uniques { .. } |
+							Call::SelendraXcm { .. } |
+							Call::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+							Call::Vesting(pallet_vesting::Call::force_vested_transfer { .. }) |
+							Call::Subscription(
+								pallet_subscription::Call::subscribe_service { .. }
+							) $($(| $transfer_calls)+)?
+					),
+					$crate::ProxyType::CancelProxy => matches!(
+						c,
+						Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
+							Call::Utility { .. } | Call::Multisig { .. }
+					),
+					$crate::ProxyType::Collator => matches!(
+						c,
+						Call::CollatorSelection { .. } |
+							Call::Utility { .. } | Call::Multisig { .. }
+					),
+					$crate::ProxyType::Assets => matches!(
+						c,
+						Call::Assets { .. } |
+							Call::Uniques { .. } | Call::Utility { .. } |
+							Call::Multisig { .. }
+					),
+					$crate::ProxyType::Subscription => matches!(
+						c,
+						Call::Subscription { .. } | Call::Utility { .. } | Call::Multisig { .. }
+					),
+					$crate::ProxyType::Identity => matches!(
+						c,
+						Call::IndraIdentity { .. } | Call::Utility { .. } | Call::Multisig { .. }
+					),
+					$crate::ProxyType::Governance => matches!(
+						c,
+						$($governance_calls)|+ | Call::Utility { .. } | Call::Multisig { .. }
+					),
+				}
+			}
+			fn is_superset(&self, o: &Self) -> bool {
+				match (self, o) {
+					(x, y) if x == y => true,
+					($crate::ProxyType::Any, _) => true,
+					(_, $crate::ProxyType::Any) => false,
+					(
+						$crate::ProxyType::NonTransfer,
+						$crate::ProxyType::CancelProxy |
+						$crate::ProxyType::Collator |
+						$crate::ProxyType::Identity |
+						$crate::ProxyType::Governance,
+					) => true,
+					_ => false,
+				}
+			}
+		}
+	};
+}
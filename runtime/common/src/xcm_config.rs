@@ -0,0 +1,248 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot. If not, see <http://www.gnu.org/licenses/>.
+
+//! XCM configuration shared by the parachain runtimes, which differ only by their relay network.
+
+/// Declares the XCM configuration of a runtime: the asset transactors, origin converters, barrier,
+/// `XcmConfig` and router, and implements `pallet_xcm::Config` with them.
+///
+/// Must be invoked in a child module of the runtime's root, e.g. `xcm_config`, given the
+/// `NetworkId` of the relay chain:
+///
+/// ```ignore
+/// parachains_common::impl_xcm_config!(relay_network = xcm::latest::NetworkId::Selendra);
+/// ```
+#[macro_export]
+macro_rules! impl_xcm_config {
+	(relay_network = $relay_network:expr $(,)?) => {
+		use super::{
+			Balances, Call, Event, Origin, ParachainInfo, ParachainSystem, Runtime, SelendraXcm,
+			Uniques, XcmpQueue,
+		};
+		use frame_support::{
+			match_type, parameter_types,
+			traits::{Everything, Nothing, PalletInfoAccess},
+			weights::{IdentityFee, Weight},
+		};
+		use pallet_xcm::XcmPassthrough;
+		use selendra_parachain::primitives::Sibling;
+		use xcm::latest::prelude::*;
+		use xcm_builder::{
+			AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
+			AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, AsPrefixedGeneralIndex,
+			ConvertedConcreteAssetId, CurrencyAdapter, EnsureXcmOrigin, FixedWeightBounds,
+			IsConcrete, LocationInverter, NativeAsset, NonFungiblesAdapter, ParentAsSuperuser,
+			ParentIsDefault, RelayChainAsNative, SiblingParachainAsNative,
+			SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
+			SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
+		};
+		use xcm_executor::{
+			traits::{Convert, JustTry},
+			Config, XcmExecutor,
+		};
+		use $crate::{AccountId, Balance};
+
+		parameter_types! {
+			pub const RelayLocation: MultiLocation = MultiLocation::parent();
+			pub RelayNetwork: NetworkId = $relay_network;
+			pub RelayChainOrigin: Origin = cumulus_pallet_xcm::Origin::Relay.into();
+			pub Ancestry: MultiLocation = Parachain(ParachainInfo::parachain_id().into()).into();
+			pub const Local: MultiLocation = Here.into();
+			pub CheckingAccount: AccountId = SelendraXcm::check_account();
+			pub UniquesPalletLocation: MultiLocation =
+				PalletInstance(<Uniques as PalletInfoAccess>::index() as u8).into();
+		}
+
+		/// Type for specifying how a `MultiLocation` can be converted into an `AccountId`. This is
+		/// used when determining ownership of accounts for asset transacting and when attempting to
+		/// use XCM `Transact` in order to determine the dispatch Origin.
+		pub type LocationToAccountId = (
+			// The parent (Relay-chain) origin converts to the default `AccountId`.
+			ParentIsDefault<AccountId>,
+			// Sibling parachain origins convert to AccountId via the `ParaId::into`.
+			SiblingParachainConvertsVia<Sibling, AccountId>,
+			// Straight up local `AccountId32` origins just alias directly to `AccountId`.
+			AccountId32Aliases<RelayNetwork, AccountId>,
+		);
+
+		/// Means for transacting the native currency on this chain.
+		pub type CurrencyTransactor = CurrencyAdapter<
+			// Use this currency:
+			Balances,
+			// Use this currency when it is a fungible asset matching the given location or name:
+			IsConcrete<RelayLocation>,
+			// Convert an XCM MultiLocation into a local account id:
+			LocationToAccountId,
+			// Our chain's account ID type (we can't get away without mentioning it explicitly):
+			AccountId,
+			// We don't track any teleports of `Balances`.
+			(),
+		>;
+
+		/// Converts an `AssetInstance::Index` into the id of an instance of the uniques pallet.
+		pub struct IndexAsInstanceId;
+		impl Convert<AssetInstance, u32> for IndexAsInstanceId {
+			fn convert(instance: AssetInstance) -> Result<u32, AssetInstance> {
+				match instance {
+					AssetInstance::Index(index) => index.try_into().map_err(|_| instance),
+					_ => Err(instance),
+				}
+			}
+
+			fn reverse(id: u32) -> Result<AssetInstance, u32> {
+				Ok(AssetInstance::Index(id.into()))
+			}
+		}
+
+		/// Means for transacting the NFTs of the uniques pallet on this chain.
+		pub type UniquesTransactor = NonFungiblesAdapter<
+			// Use this non-fungibles implementation:
+			Uniques,
+			// Use this non-fungibles implementation when it is a class under the uniques pallet's
+			// location, indexed by class id, and an instance indexed by instance id:
+			ConvertedConcreteAssetId<
+				u32,
+				u32,
+				AsPrefixedGeneralIndex<UniquesPalletLocation, u32, JustTry>,
+				IndexAsInstanceId,
+			>,
+			// Convert an XCM MultiLocation into a local account id:
+			LocationToAccountId,
+			// Our chain's account ID type (we can't get away without mentioning it explicitly):
+			AccountId,
+			// We only allow reserve transfers of NFTs, so don't track any teleports:
+			Nothing,
+			// The account used to track teleports, unused as per the above:
+			CheckingAccount,
+		>;
+
+		/// Means for transacting assets on this chain.
+		pub type AssetTransactors = (CurrencyTransactor, UniquesTransactor);
+
+		/// This is the type we use to convert an (incoming) XCM origin into a local `Origin`
+		/// instance, ready for dispatching a transaction with Xcm's `Transact`. There is an
+		/// `OriginKind` which can biases the kind of local `Origin` it will become.
+		pub type XcmOriginToTransactDispatchOrigin = (
+			// Sovereign account converter; this attempts to derive an `AccountId` from the origin
+			// location using `LocationToAccountId` and then turn that into the usual `Signed`
+			// origin. Useful for foreign chains who want to have a local sovereign account on this
+			// chain which they control.
+			SovereignSignedViaLocation<LocationToAccountId, Origin>,
+			// Native converter for Relay-chain (Parent) location; will convert to a `Relay` origin
+			// when recognised.
+			RelayChainAsNative<RelayChainOrigin, Origin>,
+			// Native converter for sibling Parachains; will convert to a `SiblingPara` origin when
+			// recognised.
+			SiblingParachainAsNative<cumulus_pallet_xcm::Origin, Origin>,
+			// Superuser converter for the Relay-chain (Parent) location. This will allow it to
+			// issue a transaction from the Root origin.
+			ParentAsSuperuser<Origin>,
+			// Native signed account converter; this just converts an `AccountId32` origin into a
+			// normal `Origin::Signed` origin of the same 32-byte value.
+			SignedAccountId32AsNative<RelayNetwork, Origin>,
+			// Xcm origins can be represented natively under the Xcm pallet's Xcm origin.
+			XcmPassthrough<Origin>,
+		);
+
+		parameter_types! {
+			// One XCM operation is 1_000_000_000 weight - almost certainly a conservative estimate.
+			pub UnitWeightCost: Weight = 1_000_000_000;
+			pub const MaxInstructions: u32 = 100;
+		}
+
+		match_type! {
+			pub type ParentOrParentsExecutivePlurality: impl Contains<MultiLocation> = {
+				MultiLocation { parents: 1, interior: Here } |
+				MultiLocation { parents: 1, interior: X1(Plurality { id: BodyId::Executive, .. }) }
+			};
+		}
+		match_type! {
+			pub type ParentOrSiblings: impl Contains<MultiLocation> = {
+				MultiLocation { parents: 1, interior: Here } |
+				MultiLocation { parents: 1, interior: X1(_) }
+			};
+		}
+
+		pub type Barrier = (
+			TakeWeightCredit,
+			AllowTopLevelPaidExecutionFrom<Everything>,
+			// Parent and its exec plurality get free execution
+			AllowUnpaidExecutionFrom<ParentOrParentsExecutivePlurality>,
+			// Expected responses are OK.
+			AllowKnownQueryResponses<SelendraXcm>,
+			// Subscriptions for version tracking are OK.
+			AllowSubscriptionsFrom<ParentOrSiblings>,
+		);
+
+		pub struct XcmConfig;
+		impl Config for XcmConfig {
+			type Call = Call;
+			type XcmSender = XcmRouter;
+			type AssetTransactor = AssetTransactors;
+			type OriginConverter = XcmOriginToTransactDispatchOrigin;
+			type IsReserve = NativeAsset;
+			// Should be enough to allow teleportation of the native token.
+			type IsTeleporter = NativeAsset;
+			type LocationInverter = LocationInverter<Ancestry>;
+			type Barrier = Barrier;
+			type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+			type Trader =
+				UsingComponents<IdentityFee<Balance>, RelayLocation, AccountId, Balances, ()>;
+			type ResponseHandler = SelendraXcm;
+			type AssetTrap = SelendraXcm;
+			type AssetClaims = SelendraXcm;
+			type SubscriptionService = SelendraXcm;
+		}
+
+		parameter_types! {
+			pub const MaxDownwardMessageWeight: Weight = $crate::MAXIMUM_BLOCK_WEIGHT / 10;
+		}
+
+		/// Converts a local signed origin into an XCM multilocation.
+		/// Forms the basis for local origins sending/executing XCMs.
+		pub type LocalOriginToLocation = SignedToAccountId32<Origin, AccountId, RelayNetwork>;
+
+		/// The means for routing XCM messages which are not for local execution into the right
+		/// message queues.
+		pub type XcmRouter = (
+			// Two routers - use UMP to communicate with the relay chain:
+			cumulus_primitives_utility::ParentAsUmp<ParachainSystem, SelendraXcm>,
+			// ..and XCMP to communicate with the sibling chains.
+			XcmpQueue,
+		);
+
+		impl pallet_xcm::Config for Runtime {
+			type Event = Event;
+			// We want to disallow users sending (arbitrary) XCMs from this chain.
+			type SendXcmOrigin = EnsureXcmOrigin<Origin, ()>;
+			type XcmRouter = XcmRouter;
+			// We support local origins dispatching XCM executions in principle...
+			type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
+			// ... but disallow generic XCM execution. As a result only teleports and reserve
+			// transfers are allowed.
+			type XcmExecuteFilter = Nothing;
+			type XcmExecutor = XcmExecutor<XcmConfig>;
+			type XcmTeleportFilter = Everything;
+			type XcmReserveTransferFilter = Everything;
+			type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
+			type LocationInverter = LocationInverter<Ancestry>;
+			type Origin = Origin;
+			type Call = Call;
+			const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+			type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+		}
+	};
+}
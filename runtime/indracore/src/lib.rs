@@ -23,15 +23,18 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub mod constants;
-mod weights;
 
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::KeyTypeId,
+	u32_trait::{_1, _2, _3, _4, _5},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT},
+	traits::Block as BlockT,
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	ApplyExtrinsicResult, Perbill,
 };
 
 use sp_std::prelude::*;
@@ -39,26 +42,20 @@ use sp_std::prelude::*;
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
-use codec::{Decode, Encode, MaxEncodedLen};
 use constants::{currency::*, fee::WeightToFee};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{EnsureOneOf, InstanceFilter},
-	weights::{
-		constants::{BlockExecutionWeight, ExtrinsicBaseWeight},
-		DispatchClass, Weight,
-	},
-	PalletId, RuntimeDebug,
-};
-use frame_system::{
-	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	traits::{ChangeMembers, EnsureOneOf, EqualPrivilegeOnly, Nothing, OnRuntimeUpgrade},
+	weights::Weight,
+	PalletId,
 };
+use frame_system::EnsureRoot;
 pub use parachains_common as common;
+pub use parachains_common::ProxyType;
 use parachains_common::{
-	impls::DealWithFees, opaque, AccountId, AuraId, Balance, BlockNumber, Hash, Header, Index,
-	Signature, AVERAGE_ON_INITIALIZE_RATIO, HOURS, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO,
-	SLOT_DURATION,
+	impls::{AssetTxPaymentApi, InitStorageVersion},
+	opaque, AccountId, AssetId, AuraId, Balance, BlockNumber, Hash, Header, Index, Signature, DAYS,
+	HOURS,
 };
 
 #[cfg(any(feature = "std", test))]
@@ -66,9 +63,6 @@ pub use sp_runtime::BuildStorage;
 
 // Selendra imports
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody};
-use selendra_runtime_common::{BlockHashCount, RocksDbWeight, SlowAdjustingFeeUpdate};
-use xcm::latest::prelude::*;
-use xcm_executor::XcmExecutor;
 
 // XCM configurations.
 pub mod xcm_config;
@@ -84,10 +78,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("indracore"),
 	impl_name: create_runtime_str!("indracore"),
 	authoring_version: 1,
-	spec_version: 001,
+	spec_version: 2,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 4,
+	transaction_version: 5,
 	state_version: 0,
 };
 
@@ -97,318 +91,264 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
-parameter_types! {
-	pub const Version: RuntimeVersion = VERSION;
-	pub RuntimeBlockLength: BlockLength =
-		BlockLength::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
-	pub RuntimeBlockWeights: BlockWeights = BlockWeights::builder()
-		.base_block(BlockExecutionWeight::get())
-		.for_class(DispatchClass::all(), |weights| {
-			weights.base_extrinsic = ExtrinsicBaseWeight::get();
-		})
-		.for_class(DispatchClass::Normal, |weights| {
-			weights.max_total = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
-		})
-		.for_class(DispatchClass::Operational, |weights| {
-			weights.max_total = Some(MAXIMUM_BLOCK_WEIGHT);
-			// Operational transactions have some extra reserved space, so that they
-			// are included even if block reached `MAXIMUM_BLOCK_WEIGHT`.
-			weights.reserved = Some(
-				MAXIMUM_BLOCK_WEIGHT - NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT
-			);
-		})
-		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
-		.build_or_panic();
-	pub const SS58Prefix: u16 = 972;
-}
-
-// Configure FRAME pallets to include in runtime.
-impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = RuntimeBlockWeights;
-	type BlockLength = RuntimeBlockLength;
-	type AccountId = AccountId;
-	type Call = Call;
-	type Lookup = AccountIdLookup<AccountId, ()>;
-	type Index = Index;
-	type BlockNumber = BlockNumber;
-	type Hash = Hash;
-	type Hashing = BlakeTwo256;
-	type Header = Header;
-	type Event = Event;
-	type Origin = Origin;
-	type BlockHashCount = BlockHashCount;
-	type DbWeight = RocksDbWeight;
-	type Version = Version;
-	type PalletInfo = PalletInfo;
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type AccountData = pallet_balances::AccountData<Balance>;
-	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
-	type SS58Prefix = SS58Prefix;
-	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
-}
-
-parameter_types! {
-	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
-}
-
-impl pallet_timestamp::Config for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
-	type OnTimestampSet = ();
-	type MinimumPeriod = MinimumPeriod;
-	type WeightInfo = weights::pallet_timestamp::WeightInfo<Runtime>;
-}
-
-parameter_types! {
-	pub const UncleGenerations: u32 = 0;
+parachains_common::impl_shared_pallets! {
+	collator_selection_update_origin = GovernanceOrigin,
+	treasury_approve_origin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_3, _5, AccountId, CouncilCollective>,
+	>,
+	treasury_reject_origin = MoreThanHalfCouncil,
+	fee_split_update_origin = GovernanceOrigin,
 }
 
-impl pallet_authorship::Config for Runtime {
-	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
-	type UncleGenerations = UncleGenerations;
-	type FilterUncle = ();
-	type EventHandler = (CollatorSelection,);
+parachains_common::impl_proxy_filter! {
+	governance_calls = Call::Democracy { .. } |
+		Call::Council { .. } |
+		Call::TechnicalCommittee { .. } |
+		Call::CouncilMembership { .. } |
+		Call::TechnicalMembership { .. } |
+		Call::Treasury { .. } |
+		Call::FeeSplit { .. },
 }
 
-parameter_types! {
-	pub const ExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT;
-	pub const MaxLocks: u32 = 50;
-	pub const MaxReserves: u32 = 50;
-}
-
-impl pallet_balances::Config for Runtime {
-	type MaxLocks = MaxLocks;
-	/// The type for recording an account's balance.
-	type Balance = Balance;
-	/// The ubiquitous event type.
-	type Event = Event;
-	type DustRemoval = ();
-	type ExistentialDeposit = ExistentialDeposit;
-	type AccountStore = System;
-	type WeightInfo = weights::pallet_balances::WeightInfo<Runtime>;
-	type MaxReserves = MaxReserves;
-	type ReserveIdentifier = [u8; 8];
-}
-
-parameter_types! {
-	/// Relay Chain `TransactionByteFee` / 10
-	pub const TransactionByteFee: Balance = 1 * MILLICENTS;
-	pub const OperationalFeeMultiplier: u8 = 5;
-}
-
-impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction =
-		pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees<Runtime>>;
-	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = WeightToFee;
-	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
-	type OperationalFeeMultiplier = OperationalFeeMultiplier;
-}
+/// We allow root, the council and the Relay Chain council to execute privileged operations, such
+/// as collator selection, identity registration and service approval.
+pub type GovernanceOrigin = EnsureOneOf<
+	MoreThanHalfCouncil,
+	EnsureXcm<IsMajorityOfBody<xcm_config::RelayLocation, ExecutiveBody>>,
+>;
 
 parameter_types! {
-	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
-	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
-	pub const MaxSignatories: u16 = 100;
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		RuntimeBlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
 }
 
-impl pallet_multisig::Config for Runtime {
+impl pallet_scheduler::Config for Runtime {
 	type Event = Event;
-	type Call = Call;
-	type Currency = Balances;
-	type DepositBase = DepositBase;
-	type DepositFactor = DepositFactor;
-	type MaxSignatories = MaxSignatories;
-	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
-}
-
-impl pallet_utility::Config for Runtime {
-	type Event = Event;
-	type Call = Call;
+	type Origin = Origin;
 	type PalletsOrigin = OriginCaller;
-	type WeightInfo = weights::pallet_utility::WeightInfo<Runtime>;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 }
 
 parameter_types! {
-	// One storage item; key size 32, value size 8; .
-	pub const ProxyDepositBase: Balance = deposit(1, 40);
-	// Additional storage item size of 33 bytes.
-	pub const ProxyDepositFactor: Balance = deposit(0, 33);
-	pub const MaxProxies: u16 = 32;
-	// One storage item; key size 32, value size 16
-	pub const AnnouncementDepositBase: Balance = deposit(1, 48);
-	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
-	pub const MaxPending: u16 = 32;
+	pub const CouncilMotionDuration: BlockNumber = 3 * DAYS;
+	pub const CouncilMaxProposals: u32 = 100;
+	pub const CouncilMaxMembers: u32 = 100;
 }
 
-/// The type used to represent the kinds of proxying allowed.
-#[derive(
-	Copy,
-	Clone,
-	Eq,
-	PartialEq,
-	Ord,
-	PartialOrd,
-	Encode,
-	Decode,
-	RuntimeDebug,
-	MaxEncodedLen,
-	scale_info::TypeInfo,
-)]
-pub enum ProxyType {
-	/// Fully permissioned proxy. Can execute any call on behalf of _proxied_.
-	Any,
-	/// Can execute any call that does not transfer funds or assets.
-	NonTransfer,
-	/// Proxy with the ability to reject time-delay proxy announcements.
-	CancelProxy,
-	/// Collator selection proxy. Can execute calls related to collator selection mechanism.
-	Collator,
-}
-impl Default for ProxyType {
-	fn default() -> Self {
-		Self::Any
-	}
-}
-impl InstanceFilter<Call> for ProxyType {
-	fn filter(&self, c: &Call) -> bool {
-		match self {
-			ProxyType::Any => true,
-			ProxyType::NonTransfer => !matches!(c, Call::Balances { .. }),
-			ProxyType::CancelProxy => matches!(
-				c,
-				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
-					Call::Utility { .. } | Call::Multisig { .. }
-			),
-			ProxyType::Collator => matches!(
-				c,
-				Call::CollatorSelection { .. } | Call::Utility { .. } | Call::Multisig { .. }
-			),
-		}
-	}
-	fn is_superset(&self, o: &Self) -> bool {
-		match (self, o) {
-			(x, y) if x == y => true,
-			(ProxyType::Any, _) => true,
-			(_, ProxyType::Any) => false,
-			_ => false,
-		}
-	}
-}
-
-impl pallet_proxy::Config for Runtime {
+pub type CouncilCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
 	type Event = Event;
-	type Call = Call;
-	type Currency = Balances;
-	type ProxyType = ProxyType;
-	type ProxyDepositBase = ProxyDepositBase;
-	type ProxyDepositFactor = ProxyDepositFactor;
-	type MaxProxies = MaxProxies;
-	type WeightInfo = weights::pallet_proxy::WeightInfo<Runtime>;
-	type MaxPending = MaxPending;
-	type CallHasher = BlakeTwo256;
-	type AnnouncementDepositBase = AnnouncementDepositBase;
-	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type MotionDuration = CouncilMotionDuration;
+	type MaxProposals = CouncilMaxProposals;
+	type MaxMembers = CouncilMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
-	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
-	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
+	pub const TechnicalMotionDuration: BlockNumber = 3 * DAYS;
+	pub const TechnicalMaxProposals: u32 = 100;
+	pub const TechnicalMaxMembers: u32 = 100;
 }
 
-impl cumulus_pallet_parachain_system::Config for Runtime {
+pub type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
 	type Event = Event;
-	type OnSystemEvent = ();
-	type SelfParaId = parachain_info::Pallet<Runtime>;
-	type DmpMessageHandler = DmpQueue;
-	type ReservedDmpWeight = ReservedDmpWeight;
-	type OutboundXcmpMessageSource = XcmpQueue;
-	type XcmpMessageHandler = XcmpQueue;
-	type ReservedXcmpWeight = ReservedXcmpWeight;
+	type MotionDuration = TechnicalMotionDuration;
+	type MaxProposals = TechnicalMaxProposals;
+	type MaxMembers = TechnicalMaxMembers;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
 }
 
-impl parachain_info::Config for Runtime {}
-
-impl cumulus_pallet_aura_ext::Config for Runtime {}
+/// Root, or more than half of the council.
+pub type MoreThanHalfCouncil = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>,
+>;
 
-impl cumulus_pallet_xcm::Config for Runtime {
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-}
-
-impl cumulus_pallet_xcmp_queue::Config for Runtime {
+	type AddOrigin = MoreThanHalfCouncil;
+	type RemoveOrigin = MoreThanHalfCouncil;
+	type SwapOrigin = MoreThanHalfCouncil;
+	type ResetOrigin = MoreThanHalfCouncil;
+	type PrimeOrigin = MoreThanHalfCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = CouncilMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_membership::Config<pallet_membership::Instance2> for Runtime {
 	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-	type ChannelInfo = ParachainSystem;
-	type VersionWrapper = SelendraXcm;
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type AddOrigin = MoreThanHalfCouncil;
+	type RemoveOrigin = MoreThanHalfCouncil;
+	type SwapOrigin = MoreThanHalfCouncil;
+	type ResetOrigin = MoreThanHalfCouncil;
+	type PrimeOrigin = MoreThanHalfCouncil;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+	type MaxMembers = TechnicalMaxMembers;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
-impl cumulus_pallet_dmp_queue::Config for Runtime {
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 7 * DAYS;
+	pub const VotingPeriod: BlockNumber = 7 * DAYS;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * HOURS;
+	pub const EnactmentPeriod: BlockNumber = 8 * DAYS;
+	pub const CooloffPeriod: BlockNumber = 7 * DAYS;
+	pub const MinimumDeposit: Balance = 100 * UNITS;
+	pub const PreimageByteDeposit: Balance = deposit(0, 1);
+	pub const InstantAllowed: bool = true;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxProposals: u32 = 100;
+}
+
+impl pallet_democracy::Config for Runtime {
+	type Proposal = Call;
 	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
+	type Currency = Balances;
+	type EnactmentPeriod = EnactmentPeriod;
+	type VoteLockingPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	/// A straight majority of the council can decide what their next motion is.
+	type ExternalOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>,
+	>;
+	/// A 3/4 super-majority of the council can have the next scheduled referendum be a straight
+	/// majority-carries vote.
+	type ExternalMajorityOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_3, _4, AccountId, CouncilCollective>,
+	>;
+	/// A unanimous council can have the next scheduled referendum be a straight default-carries
+	/// (NTB) vote.
+	type ExternalDefaultOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, CouncilCollective>,
+	>;
+	/// Two thirds of the technical committee can have an external majority or default vote
+	/// tabled immediately and with a shorter voting and enactment period.
+	type FastTrackOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>,
+	>;
+	type InstantOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>,
+	>;
+	type InstantAllowed = InstantAllowed;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	/// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
+	type CancellationOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// To cancel a proposal before it has been passed, the technical committee must be
+	/// unanimous or root must agree.
+	type CancelProposalOrigin = EnsureOneOf<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>,
+	>;
+	/// Any single technical committee member may veto a coming council proposal, however they
+	/// can only do it once and it lasts only for the cooloff period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type OperationalPreimageOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type Slash = Treasury;
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = MaxVotes;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = MaxProposals;
 }
 
 parameter_types! {
-	pub const Period: u32 = 6 * HOURS;
-	pub const Offset: u32 = 0;
-	pub const MaxAuthorities: u32 = 100_000;
-}
-
-impl pallet_session::Config for Runtime {
+	// Minimum 4 CENTS/byte
+	pub const BasicDeposit: Balance = deposit(1, 258);
+	pub const FieldDeposit: Balance = deposit(0, 66);
+	pub const SubAccountDeposit: Balance = deposit(1, 53);
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxUseridentities: u32 = 2;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+	pub const MaxEmailsize: u32 = 32;
+	pub const MaxTokenid: u32 = 32;
+	pub const MaxAccessTokenMetadata: u32 = 1;
+	pub const MaxServiceGrants: u32 = 16;
+	pub const IdentityPalletId: PalletId = PalletId(*b"sel/refr");
+	pub const ReferralReward: Balance = UNITS;
+	pub const ReferralEraLength: BlockNumber = DAYS;
+	pub const MaxReferralsPerEra: u32 = 10;
+}
+
+impl pallet_studentid::Config for Runtime {
 	type Event = Event;
-	type ValidatorId = <Self as frame_system::Config>::AccountId;
-	// we don't have stash and controller, thus we don't need the convert as well.
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
-	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
-	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
-	type SessionManager = CollatorSelection;
-	// Essentially just Aura, but lets be pedantic.
-	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
-	type Keys = SessionKeys;
-	type WeightInfo = weights::pallet_session::WeightInfo<Runtime>;
-}
-
-impl pallet_aura::Config for Runtime {
-	type AuthorityId = AuraId;
-	type DisabledValidators = ();
-	type MaxAuthorities = MaxAuthorities;
+	type Currency = Balances;
+	type Slashed = Treasury;
+	type BasicDeposit = BasicDeposit;
+	type FieldDeposit = FieldDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxUseridentities = MaxUseridentities;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type MaxEmailsize = MaxEmailsize;
+	type MaxAccessTokenMetadata = MaxAccessTokenMetadata;
+	type MaxTokenid = MaxTokenid;
+	type MaxServiceGrants = MaxServiceGrants;
+	type PalletId = IdentityPalletId;
+	type ReferralReward = ReferralReward;
+	type ReferralEraLength = ReferralEraLength;
+	type MaxReferralsPerEra = MaxReferralsPerEra;
+	// `CheckVerifiedIdentity` isn't part of the `SignedExtra`.
+	type IdentityRequiredCalls = Nothing;
+	type RegistrarOrigin = GovernanceOrigin;
+	type ForceOrigin = GovernanceOrigin;
 }
 
 parameter_types! {
-	pub const PotId: PalletId = PalletId(*b"PotStake");
-	pub const MaxCandidates: u32 = 1000;
-	pub const MinCandidates: u32 = 5;
-	pub const SessionLength: BlockNumber = 6 * HOURS;
-	pub const MaxInvulnerables: u32 = 100;
-	pub const ExecutiveBody: BodyId = BodyId::Executive;
+	// 258 bytes on-chain
+	pub const BaseDeposit: Balance = deposit(1, 258);
+	pub const MaxPublishing: u32 = 100;
+	pub const MaximumNameLength: u32 = 100;
+	pub const MaximumContractLength: u32 = 256;
+	pub const MaximumDescriptionLength: u32 = 1275;
+	pub const DataDepositPerByte: Balance = deposit(0, 1);
 }
 
-/// We allow root and the Relay Chain council to execute privileged collator selection operations.
-pub type CollatorSelectionUpdateOrigin = EnsureOneOf<
-	EnsureRoot<AccountId>,
-	EnsureXcm<IsMajorityOfBody<xcm_config::SelLocation, ExecutiveBody>>,
->;
-
-impl pallet_collator_selection::Config for Runtime {
+impl pallet_subscription::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
-	type UpdateOrigin = CollatorSelectionUpdateOrigin;
-	type PotId = PotId;
-	type MaxCandidates = MaxCandidates;
-	type MinCandidates = MinCandidates;
-	type MaxInvulnerables = MaxInvulnerables;
-	// should be a multiple of session or things will get inconsistent
-	type KickThreshold = Period;
-	type ValidatorId = <Self as frame_system::Config>::AccountId;
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
-	type ValidatorRegistration = Session;
-	type WeightInfo = weights::pallet_collator_selection::WeightInfo<Runtime>;
+	type BaseDeposit = BaseDeposit;
+	type MaxPublishing = MaxPublishing;
+	type MaximumNameLength = MaximumNameLength;
+	type MaximumContractLength = MaximumContractLength;
+	type MaximumDescriptionLength = MaximumDescriptionLength;
+	type ApproveOrigin = GovernanceOrigin;
+	type ForceOrigin = GovernanceOrigin;
+	type PublishOrigin = pallet_studentid::EnsureVerifiedIdentity<Runtime>;
+	type OnSlash = Treasury;
+	type DataDepositPerByte = DataDepositPerByte;
+	type WeightInfo = pallet_subscription::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -430,6 +370,10 @@ construct_runtime!(
 		// Monetary stuff.
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet} = 12,
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 13,
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>} = 14,
+		FeeSplit: pallet_fee_split::{Pallet, Call, Storage, Event} = 15,
 
 		// Collator support. the order of these 5 are important and shall not change.
 		Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
@@ -448,9 +392,27 @@ construct_runtime!(
 		Utility: pallet_utility::{Pallet, Call, Event} = 40,
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 41,
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 42,
+
+		// Identity
+		IndraIdentity: pallet_studentid::{Pallet, Call, Storage, Event<T>} = 50,
+		Subscription: pallet_subscription::{Pallet, Call, Storage, Event<T>} = 51,
+
+		// The main stage.
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>} = 52,
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 53,
+
+		// Governance.
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>} = 61,
+		Democracy: pallet_democracy::{Pallet, Call, Storage, Config<T>, Event<T>} = 62,
+		Council: pallet_collective::<Instance1>::{Pallet, Call, Storage, Origin<T>, Event<T>} = 63,
+		TechnicalCommittee: pallet_collective::<Instance2>::{Pallet, Call, Storage, Origin<T>, Event<T>} = 64,
+		CouncilMembership: pallet_membership::<Instance1>::{Pallet, Call, Storage, Event<T>, Config<T>} = 65,
+		TechnicalMembership: pallet_membership::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 66,
 	}
 );
 
+/// The identity record returned by the identity runtime API.
+pub type IdentityRegistration = pallet_studentid::RegistrationOf<Runtime>;
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block type as expected by this runtime.
@@ -468,7 +430,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -481,8 +443,67 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	InitStorageVersion<Runtime, Treasury>,
+	InitStorageVersion<Runtime, Vesting>,
+	InitStorageVersion<Runtime, FeeSplit>,
+	InitStorageVersion<Runtime, IndraIdentity>,
+	InitStorageVersion<Runtime, Subscription>,
+	InitStorageVersion<Runtime, Assets>,
+	InitStorageVersion<Runtime, Uniques>,
+	InitStorageVersion<Runtime, Scheduler>,
+	InitStorageVersion<Runtime, Democracy>,
+	InitStorageVersion<Runtime, Council>,
+	InitStorageVersion<Runtime, TechnicalCommittee>,
+	InitStorageVersion<Runtime, CouncilMembership>,
+	InitStorageVersion<Runtime, TechnicalMembership>,
+	InitGovernanceMembers,
+);
+
+/// Migration seeding the council and the technical committee with the invulnerable collators.
+///
+/// Both are added to a running chain, which never runs their genesis build, so they would
+/// otherwise start without members and no motion could ever pass. A body which already has
+/// members is left untouched.
+pub struct InitGovernanceMembers;
+
+impl InitGovernanceMembers {
+	/// Seed the membership pallet instance `I`, and the collective `C` it manages, with
+	/// `members` unless it already has members.
+	fn init<I: 'static, C: ChangeMembers<AccountId>>(members: &[AccountId]) -> Weight
+	where
+		Runtime: pallet_membership::Config<I>,
+	{
+		type DbWeight = <Runtime as frame_system::Config>::DbWeight;
+		if !pallet_membership::Members::<Runtime, I>::get().is_empty() {
+			return DbWeight::get().reads(1)
+		}
+		pallet_membership::Members::<Runtime, I>::put(members);
+		C::set_members_sorted(members, &[]);
+		DbWeight::get().reads_writes(2, 3)
+	}
+}
+
+impl OnRuntimeUpgrade for InitGovernanceMembers {
+	fn on_runtime_upgrade() -> Weight {
+		let mut members = CollatorSelection::invulnerables();
+		members.sort();
+		<Runtime as frame_system::Config>::DbWeight::get().reads(1) +
+			Self::init::<pallet_membership::Instance1, Council>(&members) +
+			Self::init::<pallet_membership::Instance2, TechnicalCommittee>(&members)
+	}
+}
+
+/// The asset `uxt` pays its fees in, if it does not pay them in the native currency.
+fn fee_asset(uxt: &UncheckedExtrinsic) -> Option<AssetId> {
+	let (_, _, extra) = uxt.signature.as_ref()?;
+	parachains_common::impls::fee_asset(&extra.7)
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -492,12 +513,20 @@ mod benches {
 	define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
+		[pallet_uniques, Uniques]
 		[pallet_multisig, Multisig]
 		[pallet_proxy, Proxy]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_utility, Utility]
 		[pallet_timestamp, Timestamp]
 		[pallet_collator_selection, CollatorSelection]
+		[pallet_treasury, Treasury]
+		[pallet_vesting, Vesting]
+		[pallet_scheduler, Scheduler]
+		[pallet_democracy, Democracy]
+		[pallet_collective, Council]
+		[pallet_membership, CouncilMembership]
 	);
 }
 
@@ -592,13 +621,32 @@ impl_runtime_apis! {
 			uxt: <Block as BlockT>::Extrinsic,
 			len: u32,
 		) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			let asset_id = fee_asset(&uxt);
+			AssetTxPaymentApi::<Runtime>::query_info(uxt, len, asset_id)
 		}
 		fn query_fee_details(
 			uxt: <Block as BlockT>::Extrinsic,
 			len: u32,
 		) -> pallet_transaction_payment::FeeDetails<Balance> {
-			TransactionPayment::query_fee_details(uxt, len)
+			let asset_id = fee_asset(&uxt);
+			AssetTxPaymentApi::<Runtime>::query_fee_details(uxt, len, asset_id)
+		}
+	}
+
+	impl pallet_studentid_rpc_runtime_api::IdentityApi<Block, AccountId, IdentityRegistration> for Runtime {
+		fn identity_of(who: AccountId) -> Option<IdentityRegistration> {
+			IndraIdentity::identity_of(&who)
+		}
+		fn account_of(email_hash: sp_core::H256) -> Option<AccountId> {
+			IndraIdentity::account_of(email_hash.as_fixed_bytes())
+		}
+		fn has_service_access(who: AccountId, service: Vec<u8>) -> bool {
+			service.try_into().map_or(false, |service| {
+				IndraIdentity::has_service_access(&who, &pallet_studentid::Data::Raw(service))
+			})
+		}
+		fn judgements_of(who: AccountId) -> Vec<(pallet_studentid::RegistrarIndex, pallet_studentid::Judgement)> {
+			IndraIdentity::judgements_of(&who)
 		}
 	}
 
@@ -701,3 +749,215 @@ cumulus_pallet_parachain_system::register_validate_block! {
 	BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
 	CheckInherents = CheckInherents,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::traits::InstanceFilter;
+	use sp_runtime::AccountId32;
+	use xcm::latest::prelude::*;
+
+	const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+
+	fn remark() -> Call {
+		Call::System(frame_system::Call::remark { remark: vec![] })
+	}
+
+	fn batch(call: Call) -> Call {
+		Call::Utility(pallet_utility::Call::batch { calls: vec![call] })
+	}
+
+	fn balances_transfer() -> Call {
+		Call::Balances(pallet_balances::Call::transfer { dest: ALICE.into(), value: 1 })
+	}
+
+	fn vested_transfer() -> Call {
+		Call::Vesting(pallet_vesting::Call::vested_transfer {
+			target: ALICE.into(),
+			schedule: pallet_vesting::VestingInfo::new(10 * UNITS, UNITS, 0),
+		})
+	}
+
+	fn vest() -> Call {
+		Call::Vesting(pallet_vesting::Call::vest {})
+	}
+
+	fn assets_transfer() -> Call {
+		Call::Assets(pallet_assets::Call::transfer { id: 1, target: ALICE.into(), amount: 1 })
+	}
+
+	fn uniques_transfer() -> Call {
+		Call::Uniques(pallet_uniques::Call::transfer { class: 1, instance: 1, dest: ALICE.into() })
+	}
+
+	fn xcm_teleport() -> Call {
+		Call::SelendraXcm(pallet_xcm::Call::teleport_assets {
+			dest: Box::new(MultiLocation::parent().into()),
+			beneficiary: Box::new(MultiLocation::here().into()),
+			assets: Box::new(MultiAssets::from(MultiAsset::from((Parent, 1u128))).into()),
+			fee_asset_item: 0,
+		})
+	}
+
+	fn subscribe_service() -> Call {
+		Call::Subscription(pallet_subscription::Call::subscribe_service { service_id: 0 })
+	}
+
+	fn request_approved_publisher() -> Call {
+		Call::Subscription(pallet_subscription::Call::request_approved_publisher {})
+	}
+
+	fn identity_login() -> Call {
+		Call::IndraIdentity(pallet_studentid::Call::login_web3_sel16 { email: b"a@b.c".to_vec() })
+	}
+
+	fn register_as_candidate() -> Call {
+		Call::CollatorSelection(pallet_collator_selection::Call::register_as_candidate {})
+	}
+
+	fn reject_announcement() -> Call {
+		Call::Proxy(pallet_proxy::Call::reject_announcement {
+			delegate: ALICE,
+			call_hash: Default::default(),
+		})
+	}
+
+	fn governance_calls() -> Vec<Call> {
+		vec![
+			Call::Democracy(pallet_democracy::Call::second { proposal: 0, seconds_upper_bound: 1 }),
+			Call::Council(pallet_collective::Call::vote {
+				proposal: Default::default(),
+				index: 0,
+				approve: true,
+			}),
+			Call::TechnicalCommittee(pallet_collective::Call::vote {
+				proposal: Default::default(),
+				index: 0,
+				approve: true,
+			}),
+			Call::CouncilMembership(pallet_membership::Call::add_member { who: ALICE }),
+			Call::TechnicalMembership(pallet_membership::Call::add_member { who: ALICE }),
+			Call::Treasury(pallet_treasury::Call::propose_spend {
+				value: 1,
+				beneficiary: ALICE.into(),
+			}),
+			Call::FeeSplit(pallet_fee_split::Call::set_fee_split {
+				treasury_share: Default::default(),
+				burn_share: Default::default(),
+			}),
+		]
+	}
+
+	fn value_moving_calls() -> Vec<Call> {
+		vec![
+			balances_transfer(),
+			vested_transfer(),
+			assets_transfer(),
+			uniques_transfer(),
+			xcm_teleport(),
+			subscribe_service(),
+		]
+	}
+
+	#[test]
+	fn any_proxy_allows_everything() {
+		for call in value_moving_calls().into_iter().chain(governance_calls()) {
+			assert!(ProxyType::Any.filter(&call));
+		}
+		assert!(ProxyType::Any.filter(&remark()));
+	}
+
+	#[test]
+	fn non_transfer_proxy_excludes_value_moving_calls() {
+		for call in value_moving_calls() {
+			assert!(!ProxyType::NonTransfer.filter(&call));
+		}
+		for call in governance_calls() {
+			assert!(ProxyType::NonTransfer.filter(&call));
+		}
+		assert!(ProxyType::NonTransfer.filter(&remark()));
+		assert!(ProxyType::NonTransfer.filter(&vest()));
+		assert!(ProxyType::NonTransfer.filter(&request_approved_publisher()));
+		assert!(ProxyType::NonTransfer.filter(&identity_login()));
+		assert!(ProxyType::NonTransfer.filter(&register_as_candidate()));
+	}
+
+	#[test]
+	fn cancel_proxy_only_rejects_announcements() {
+		assert!(ProxyType::CancelProxy.filter(&reject_announcement()));
+		assert!(ProxyType::CancelProxy.filter(&batch(reject_announcement())));
+		assert!(!ProxyType::CancelProxy.filter(&remark()));
+		assert!(!ProxyType::CancelProxy.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn collator_proxy_is_scoped_to_collator_selection() {
+		assert!(ProxyType::Collator.filter(&register_as_candidate()));
+		assert!(!ProxyType::Collator.filter(&remark()));
+		assert!(!ProxyType::Collator.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn assets_proxy_is_scoped_to_assets_and_uniques() {
+		assert!(ProxyType::Assets.filter(&assets_transfer()));
+		assert!(ProxyType::Assets.filter(&uniques_transfer()));
+		assert!(!ProxyType::Assets.filter(&balances_transfer()));
+		assert!(!ProxyType::Assets.filter(&xcm_teleport()));
+	}
+
+	#[test]
+	fn subscription_proxy_is_scoped_to_subscription() {
+		assert!(ProxyType::Subscription.filter(&subscribe_service()));
+		assert!(ProxyType::Subscription.filter(&request_approved_publisher()));
+		assert!(!ProxyType::Subscription.filter(&balances_transfer()));
+		assert!(!ProxyType::Subscription.filter(&identity_login()));
+	}
+
+	#[test]
+	fn identity_proxy_is_scoped_to_identity() {
+		assert!(ProxyType::Identity.filter(&identity_login()));
+		assert!(!ProxyType::Identity.filter(&subscribe_service()));
+		assert!(!ProxyType::Identity.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn governance_proxy_is_scoped_to_governance() {
+		for call in governance_calls() {
+			assert!(ProxyType::Governance.filter(&call));
+		}
+		for call in value_moving_calls() {
+			assert!(!ProxyType::Governance.filter(&call));
+		}
+		assert!(!ProxyType::Governance.filter(&identity_login()));
+	}
+
+	#[test]
+	fn proxy_supersets() {
+		assert!(ProxyType::Any.is_superset(&ProxyType::Governance));
+		assert!(!ProxyType::Governance.is_superset(&ProxyType::Any));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Identity));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Subscription));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Assets));
+		assert!(!ProxyType::Subscription.is_superset(&ProxyType::Identity));
+	}
+
+	#[test]
+	fn governance_members_are_seeded_once() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			let bob = AccountId32::new([2u8; 32]);
+			pallet_collator_selection::Invulnerables::<Runtime>::put(vec![bob.clone(), ALICE]);
+
+			InitGovernanceMembers::on_runtime_upgrade();
+			assert_eq!(Council::members(), vec![ALICE, bob.clone()]);
+			assert_eq!(CouncilMembership::members(), vec![ALICE, bob.clone()]);
+			assert_eq!(TechnicalCommittee::members(), vec![ALICE, bob.clone()]);
+			assert_eq!(TechnicalMembership::members(), vec![ALICE, bob.clone()]);
+
+			// Members chosen by governance are kept.
+			assert!(CouncilMembership::remove_member(Origin::root(), bob.into()).is_ok());
+			InitGovernanceMembers::on_runtime_upgrade();
+			assert_eq!(Council::members(), vec![ALICE]);
+		});
+	}
+}
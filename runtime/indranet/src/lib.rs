@@ -23,15 +23,14 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 pub mod constants;
-mod weights;
 
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, H256, U256};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	traits::{BlakeTwo256, Block as BlockT, Dispatchable, PostDispatchInfoOf},
+	transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
+	ApplyExtrinsicResult, ConsensusEngineId, Permill,
 };
 
 use sp_std::prelude::*;
@@ -39,26 +38,21 @@ use sp_std::prelude::*;
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, Encode};
 use constants::{currency::*, fee::WeightToFee};
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{EnsureOneOf, InstanceFilter},
-	weights::{
-		constants::{BlockExecutionWeight, ExtrinsicBaseWeight},
-		DispatchClass, Weight,
-	},
-	PalletId, RuntimeDebug,
-};
-use frame_system::{
-	limits::{BlockLength, BlockWeights},
-	EnsureRoot,
+	traits::{Contains, EnsureOneOf, FindAuthor, Get, Imbalance, OnUnbalanced},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
+	PalletId,
 };
+use frame_system::EnsureRoot;
 pub use parachains_common as common;
+pub use parachains_common::ProxyType;
 use parachains_common::{
-	impls::DealWithFees, opaque, AccountId, AuraId, Balance, BlockNumber, Hash, Header, Index,
-	Signature, AVERAGE_ON_INITIALIZE_RATIO, HOURS, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO,
-	SLOT_DURATION,
+	impls::{AssetTxPaymentApi, DealWithFees, InitStorageVersion, NegativeImbalance},
+	opaque, AccountId, AssetId, AuraId, Balance, BlockNumber, Hash, Header, Index, Signature, DAYS,
+	MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO,
 };
 
 #[cfg(any(feature = "std", test))]
@@ -66,13 +60,26 @@ pub use sp_runtime::BuildStorage;
 
 // Selendra imports
 use pallet_xcm::{EnsureXcm, IsMajorityOfBody};
-use selendra_runtime_common::{BlockHashCount, RocksDbWeight, SlowAdjustingFeeUpdate};
-use xcm::latest::prelude::*;
-use xcm_executor::XcmExecutor;
+
+// Frontier imports
+use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
+use pallet_evm::{
+	Account as EVMAccount, EVMCurrencyAdapter, FeeCalculator, GasWeightMapping,
+	HashedAddressMapping, OnChargeEVMTransaction, Runner,
+};
+#[cfg(feature = "evm-tracing")]
+use pallet_evm::{TracerConfig, TransactionTrace};
+use pallet_evm_accounts::{EnsureAddressMapped, EvmAddressMapping};
+#[cfg(feature = "evm-tracing")]
+use sp_runtime::DispatchError;
 
 // XCM configurations.
 pub mod xcm_config;
 
+// EVM precompiles.
+pub mod precompiles;
+use precompiles::IndranetPrecompiles;
+
 impl_opaque_keys! {
 	pub struct SessionKeys {
 		pub aura: Aura,
@@ -84,10 +91,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("indranet"),
 	impl_name: create_runtime_str!("indranet"),
 	authoring_version: 1,
-	spec_version: 001,
+	spec_version: 2,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 4,
+	transaction_version: 5,
 	state_version: 0,
 };
 
@@ -97,320 +104,24 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
-parameter_types! {
-	pub const Version: RuntimeVersion = VERSION;
-	pub RuntimeBlockLength: BlockLength =
-		BlockLength::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
-	pub RuntimeBlockWeights: BlockWeights = BlockWeights::builder()
-		.base_block(BlockExecutionWeight::get())
-		.for_class(DispatchClass::all(), |weights| {
-			weights.base_extrinsic = ExtrinsicBaseWeight::get();
-		})
-		.for_class(DispatchClass::Normal, |weights| {
-			weights.max_total = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
-		})
-		.for_class(DispatchClass::Operational, |weights| {
-			weights.max_total = Some(MAXIMUM_BLOCK_WEIGHT);
-			// Operational transactions have some extra reserved space, so that they
-			// are included even if block reached `MAXIMUM_BLOCK_WEIGHT`.
-			weights.reserved = Some(
-				MAXIMUM_BLOCK_WEIGHT - NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT
-			);
-		})
-		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
-		.build_or_panic();
-	pub const SS58Prefix: u16 = 972;
-}
-
-// Configure FRAME pallets to include in runtime.
-impl frame_system::Config for Runtime {
-	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = RuntimeBlockWeights;
-	type BlockLength = RuntimeBlockLength;
-	type AccountId = AccountId;
-	type Call = Call;
-	type Lookup = AccountIdLookup<AccountId, ()>;
-	type Index = Index;
-	type BlockNumber = BlockNumber;
-	type Hash = Hash;
-	type Hashing = BlakeTwo256;
-	type Header = Header;
-	type Event = Event;
-	type Origin = Origin;
-	type BlockHashCount = BlockHashCount;
-	type DbWeight = RocksDbWeight;
-	type Version = Version;
-	type PalletInfo = PalletInfo;
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type AccountData = pallet_balances::AccountData<Balance>;
-	type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
-	type SS58Prefix = SS58Prefix;
-	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
-}
-
-parameter_types! {
-	pub const MinimumPeriod: u64 = SLOT_DURATION / 2;
-}
-
-impl pallet_timestamp::Config for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
-	type OnTimestampSet = ();
-	type MinimumPeriod = MinimumPeriod;
-	type WeightInfo = weights::pallet_timestamp::WeightInfo<Runtime>;
-}
-
-parameter_types! {
-	pub const UncleGenerations: u32 = 0;
-}
-
-impl pallet_authorship::Config for Runtime {
-	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
-	type UncleGenerations = UncleGenerations;
-	type FilterUncle = ();
-	type EventHandler = (CollatorSelection,);
-}
-
-parameter_types! {
-	pub const ExistentialDeposit: Balance = EXISTENTIAL_DEPOSIT;
-	pub const MaxLocks: u32 = 50;
-	pub const MaxReserves: u32 = 50;
-}
-
-impl pallet_balances::Config for Runtime {
-	type MaxLocks = MaxLocks;
-	/// The type for recording an account's balance.
-	type Balance = Balance;
-	/// The ubiquitous event type.
-	type Event = Event;
-	type DustRemoval = ();
-	type ExistentialDeposit = ExistentialDeposit;
-	type AccountStore = System;
-	type WeightInfo = weights::pallet_balances::WeightInfo<Runtime>;
-	type MaxReserves = MaxReserves;
-	type ReserveIdentifier = [u8; 8];
-}
-
-parameter_types! {
-	/// Relay Chain `TransactionByteFee` / 10
-	pub const TransactionByteFee: Balance = 1 * MILLICENTS;
-	pub const OperationalFeeMultiplier: u8 = 5;
-}
-
-impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction =
-		pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees<Runtime>>;
-	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = WeightToFee;
-	type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
-	type OperationalFeeMultiplier = OperationalFeeMultiplier;
-}
-
-parameter_types! {
-	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
-	pub const DepositBase: Balance = deposit(1, 88);
-	// Additional storage item size of 32 bytes.
-	pub const DepositFactor: Balance = deposit(0, 32);
-	pub const MaxSignatories: u16 = 100;
-}
-
-impl pallet_multisig::Config for Runtime {
-	type Event = Event;
-	type Call = Call;
-	type Currency = Balances;
-	type DepositBase = DepositBase;
-	type DepositFactor = DepositFactor;
-	type MaxSignatories = MaxSignatories;
-	type WeightInfo = weights::pallet_multisig::WeightInfo<Runtime>;
-}
-
-impl pallet_utility::Config for Runtime {
-	type Event = Event;
-	type Call = Call;
-	type PalletsOrigin = OriginCaller;
-	type WeightInfo = weights::pallet_utility::WeightInfo<Runtime>;
-}
-
-parameter_types! {
-	// One storage item; key size 32, value size 8; .
-	pub const ProxyDepositBase: Balance = deposit(1, 40);
-	// Additional storage item size of 33 bytes.
-	pub const ProxyDepositFactor: Balance = deposit(0, 33);
-	pub const MaxProxies: u16 = 32;
-	// One storage item; key size 32, value size 16
-	pub const AnnouncementDepositBase: Balance = deposit(1, 48);
-	pub const AnnouncementDepositFactor: Balance = deposit(0, 66);
-	pub const MaxPending: u16 = 32;
-}
-
-/// The type used to represent the kinds of proxying allowed.
-#[derive(
-	Copy,
-	Clone,
-	Eq,
-	PartialEq,
-	Ord,
-	PartialOrd,
-	Encode,
-	Decode,
-	RuntimeDebug,
-	MaxEncodedLen,
-	scale_info::TypeInfo,
-)]
-pub enum ProxyType {
-	/// Fully permissioned proxy. Can execute any call on behalf of _proxied_.
-	Any,
-	/// Can execute any call that does not transfer funds or assets.
-	NonTransfer,
-	/// Proxy with the ability to reject time-delay proxy announcements.
-	CancelProxy,
-	/// Collator selection proxy. Can execute calls related to collator selection mechanism.
-	Collator,
-}
-impl Default for ProxyType {
-	fn default() -> Self {
-		Self::Any
-	}
-}
-impl InstanceFilter<Call> for ProxyType {
-	fn filter(&self, c: &Call) -> bool {
-		match self {
-			ProxyType::Any => true,
-			ProxyType::NonTransfer => !matches!(c, Call::Balances { .. }),
-			ProxyType::CancelProxy => matches!(
-				c,
-				Call::Proxy(pallet_proxy::Call::reject_announcement { .. }) |
-					Call::Utility { .. } | Call::Multisig { .. }
-			),
-			ProxyType::Collator => matches!(
-				c,
-				Call::CollatorSelection { .. } | Call::Utility { .. } | Call::Multisig { .. }
-			),
-		}
-	}
-	fn is_superset(&self, o: &Self) -> bool {
-		match (self, o) {
-			(x, y) if x == y => true,
-			(ProxyType::Any, _) => true,
-			(_, ProxyType::Any) => false,
-			_ => false,
-		}
-	}
-}
-
-impl pallet_proxy::Config for Runtime {
-	type Event = Event;
-	type Call = Call;
-	type Currency = Balances;
-	type ProxyType = ProxyType;
-	type ProxyDepositBase = ProxyDepositBase;
-	type ProxyDepositFactor = ProxyDepositFactor;
-	type MaxProxies = MaxProxies;
-	type WeightInfo = weights::pallet_proxy::WeightInfo<Runtime>;
-	type MaxPending = MaxPending;
-	type CallHasher = BlakeTwo256;
-	type AnnouncementDepositBase = AnnouncementDepositBase;
-	type AnnouncementDepositFactor = AnnouncementDepositFactor;
-}
-
-parameter_types! {
-	pub const ReservedXcmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
-	pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
+parachains_common::impl_shared_pallets! {
+	collator_selection_update_origin = CollatorSelectionUpdateOrigin,
+	treasury_approve_origin = EnsureRoot<AccountId>,
+	treasury_reject_origin = EnsureRoot<AccountId>,
+	fee_split_update_origin = EnsureRoot<AccountId>,
 }
 
-impl cumulus_pallet_parachain_system::Config for Runtime {
-	type Event = Event;
-	type OnSystemEvent = ();
-	type SelfParaId = parachain_info::Pallet<Runtime>;
-	type DmpMessageHandler = DmpQueue;
-	type ReservedDmpWeight = ReservedDmpWeight;
-	type OutboundXcmpMessageSource = XcmpQueue;
-	type XcmpMessageHandler = XcmpQueue;
-	type ReservedXcmpWeight = ReservedXcmpWeight;
-}
-
-impl parachain_info::Config for Runtime {}
-
-impl cumulus_pallet_aura_ext::Config for Runtime {}
-
-impl cumulus_pallet_xcm::Config for Runtime {
-	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-}
-
-impl cumulus_pallet_xcmp_queue::Config for Runtime {
-	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-	type ChannelInfo = ParachainSystem;
-	type VersionWrapper = SelendraXcm;
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
-}
-
-impl cumulus_pallet_dmp_queue::Config for Runtime {
-	type Event = Event;
-	type XcmExecutor = XcmExecutor<xcm_config::XcmConfig>;
-	type ExecuteOverweightOrigin = EnsureRoot<AccountId>;
-}
-
-parameter_types! {
-	pub const Period: u32 = 6 * HOURS;
-	pub const Offset: u32 = 0;
-	pub const MaxAuthorities: u32 = 100_000;
-}
-
-impl pallet_session::Config for Runtime {
-	type Event = Event;
-	type ValidatorId = <Self as frame_system::Config>::AccountId;
-	// we don't have stash and controller, thus we don't need the convert as well.
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
-	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
-	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
-	type SessionManager = CollatorSelection;
-	// Essentially just Aura, but lets be pedantic.
-	type SessionHandler = <SessionKeys as sp_runtime::traits::OpaqueKeys>::KeyTypeIdProviders;
-	type Keys = SessionKeys;
-	type WeightInfo = weights::pallet_session::WeightInfo<Runtime>;
-}
-
-impl pallet_aura::Config for Runtime {
-	type AuthorityId = AuraId;
-	type DisabledValidators = ();
-	type MaxAuthorities = MaxAuthorities;
-}
-
-parameter_types! {
-	pub const PotId: PalletId = PalletId(*b"PotStake");
-	pub const MaxCandidates: u32 = 1000;
-	pub const MinCandidates: u32 = 5;
-	pub const SessionLength: BlockNumber = 6 * HOURS;
-	pub const MaxInvulnerables: u32 = 100;
-	pub const ExecutiveBody: BodyId = BodyId::Executive;
+parachains_common::impl_proxy_filter! {
+	governance_calls = Call::Treasury { .. } | Call::FeeSplit { .. },
+	transfer_calls = Call::EVM { .. } | Call::EvmAccounts { .. },
 }
 
 /// We allow root and the Relay Chain council to execute privileged collator selection operations.
 pub type CollatorSelectionUpdateOrigin = EnsureOneOf<
 	EnsureRoot<AccountId>,
-	EnsureXcm<IsMajorityOfBody<xcm_config::CardamomLocation, ExecutiveBody>>,
+	EnsureXcm<IsMajorityOfBody<xcm_config::RelayLocation, ExecutiveBody>>,
 >;
 
-impl pallet_collator_selection::Config for Runtime {
-	type Event = Event;
-	type Currency = Balances;
-	type UpdateOrigin = CollatorSelectionUpdateOrigin;
-	type PotId = PotId;
-	type MaxCandidates = MaxCandidates;
-	type MinCandidates = MinCandidates;
-	type MaxInvulnerables = MaxInvulnerables;
-	// should be a multiple of session or things will get inconsistent
-	type KickThreshold = Period;
-	type ValidatorId = <Self as frame_system::Config>::AccountId;
-	type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
-	type ValidatorRegistration = Session;
-	type WeightInfo = weights::pallet_collator_selection::WeightInfo<Runtime>;
-}
-
 parameter_types! {
 	pub const BasicDeposit: u64 = 10;
 	pub const FieldDeposit: u64 = 10;
@@ -422,13 +133,27 @@ parameter_types! {
 	pub const MaxEmailsize: u32 = 30;
 	pub const MaxTokenid: u32 = 30;
 	pub const MaxAccessTokenMetadata: u32 = 1;
+	pub const MaxServiceGrants: u32 = 16;
+	pub const IdentityPalletId: PalletId = PalletId(*b"sel/refr");
+	pub const ReferralReward: Balance = UNITS;
+	pub const ReferralEraLength: BlockNumber = DAYS;
+	pub const MaxReferralsPerEra: u32 = 10;
+}
 
+/// Calls which may only be signed by accounts with a verified identity. Nested calls are left
+/// out, since the account they are dispatched as isn't the signer: their pallets check it with
+/// [`pallet_studentid::EnsureVerifiedIdentity`] instead.
+pub struct IdentityRequiredCalls;
+impl Contains<Call> for IdentityRequiredCalls {
+	fn contains(c: &Call) -> bool {
+		matches!(c, Call::Subscription(pallet_subscription::Call::publish_service { .. }))
+	}
 }
 
 impl pallet_studentid::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
-	type Slashed = ();
+	type Slashed = Treasury;
 	type BasicDeposit = BasicDeposit;
 	type FieldDeposit = FieldDeposit;
 	type SubAccountDeposit = SubAccountDeposit;
@@ -439,6 +164,12 @@ impl pallet_studentid::Config for Runtime {
 	type MaxEmailsize = MaxEmailsize;
 	type MaxAccessTokenMetadata = MaxAccessTokenMetadata;
 	type MaxTokenid = MaxTokenid;
+	type MaxServiceGrants = MaxServiceGrants;
+	type PalletId = IdentityPalletId;
+	type ReferralReward = ReferralReward;
+	type ReferralEraLength = ReferralEraLength;
+	type MaxReferralsPerEra = MaxReferralsPerEra;
+	type IdentityRequiredCalls = IdentityRequiredCalls;
 	type RegistrarOrigin = frame_system::EnsureRoot<Self::AccountId>;
 	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
 }
@@ -462,7 +193,8 @@ impl pallet_subscription::Config for Runtime {
 	type MaximumDescriptionLength = MaximumDescriptionLength;
 	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
 	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
-	type OnSlash = ();
+	type PublishOrigin = pallet_studentid::EnsureVerifiedIdentity<Runtime>;
+	type OnSlash = Treasury;
 	type DataDepositPerByte = DataDepositPerByte;
 	type WeightInfo = pallet_subscription::weights::SubstrateWeight<Runtime>;
 }
@@ -472,6 +204,192 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+/// Current approximation of the gas per second consumption, considering EVM execution over
+/// compiled WASM.
+pub const GAS_PER_SECOND: u64 = 40_000_000;
+
+/// Approximate ratio of the amount of weight per gas.
+pub const WEIGHT_PER_GAS: u64 = WEIGHT_PER_SECOND / GAS_PER_SECOND;
+
+/// Maps EVM gas to weight at the fixed `WEIGHT_PER_GAS` rate.
+pub struct IndranetGasWeightMapping;
+impl GasWeightMapping for IndranetGasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas.saturating_mul(WEIGHT_PER_GAS)
+	}
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight.wrapping_div(WEIGHT_PER_GAS)
+	}
+}
+
+/// Finds the EVM coinbase: the EVM address bound to the block author's account, or else the
+/// account truncated to its first 20 bytes.
+pub struct FindAuthorTruncated<F>(sp_std::marker::PhantomData<F>);
+impl<F: FindAuthor<AccountId>> FindAuthor<H160> for FindAuthorTruncated<F> {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		F::find_author(digests).map(|author| {
+			EvmAccounts::evm_addresses(&author)
+				.unwrap_or_else(|| H160::from_slice(&AsRef::<[u8; 32]>::as_ref(&author)[0..20]))
+		})
+	}
+}
+
+/// Charges EVM transactions like `pallet_transaction_payment::CurrencyAdapter` charges extrinsics:
+/// `OU` receives the base fee as an extrinsic's fee and the priority tip as its tip, so that both
+/// are split alike.
+pub struct EvmCurrencyAdapter<OU>(sp_std::marker::PhantomData<OU>);
+impl<OU> OnChargeEVMTransaction<Runtime> for EvmCurrencyAdapter<OU>
+where
+	OU: OnUnbalanced<NegativeImbalance<Runtime>>,
+{
+	type LiquidityInfo = Option<NegativeImbalance<Runtime>>;
+
+	fn withdraw_fee(
+		who: &H160,
+		fee: U256,
+	) -> Result<Self::LiquidityInfo, pallet_evm::Error<Runtime>> {
+		<FrontierAdapter<OU> as OnChargeEVMTransaction<Runtime>>::withdraw_fee(who, fee)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		<FrontierAdapter<OU> as OnChargeEVMTransaction<Runtime>>::correct_and_deposit_fee(
+			who,
+			corrected_fee,
+			base_fee,
+			already_withdrawn,
+		)
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		if let Some(tip) = tip {
+			OU::on_unbalanceds([NegativeImbalance::<Runtime>::zero(), tip].into_iter());
+		}
+	}
+}
+
+/// Frontier's adapter, which `EvmCurrencyAdapter` relies on to withdraw and refund fees.
+type FrontierAdapter<OU> = EVMCurrencyAdapter<Balances, AsFees<OU>>;
+
+/// Passes the base fees `EVMCurrencyAdapter` deposits on to `OU` as fees without a tip.
+pub struct AsFees<OU>(sp_std::marker::PhantomData<OU>);
+impl<OU> OnUnbalanced<NegativeImbalance<Runtime>> for AsFees<OU>
+where
+	OU: OnUnbalanced<NegativeImbalance<Runtime>>,
+{
+	fn on_nonzero_unbalanced(fees: NegativeImbalance<Runtime>) {
+		OU::on_unbalanceds(sp_std::iter::once(fees));
+	}
+}
+
+parameter_types! {
+	/// The EIP-155 chain id of Indranet's EVM.
+	pub const ChainId: u64 = 1953;
+	/// The gas a block may use, matching the weight left to normal extrinsics.
+	pub BlockGasLimit: U256 =
+		U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT / WEIGHT_PER_GAS);
+	pub PrecompilesValue: IndranetPrecompiles<Runtime, EvmDispatchCalls> =
+		IndranetPrecompiles::<_, _>::new();
+}
+
+/// Calls which contracts may dispatch through the dispatch precompile. Calls able to dispatch
+/// other calls, such as batches, proxies and multisigs, are left out since the calls they
+/// dispatch wouldn't be checked. Calls requiring a verified identity check it through their
+/// dispatch origin, so they are checked against the contract's caller too.
+pub struct EvmDispatchCalls;
+impl Contains<Call> for EvmDispatchCalls {
+	fn contains(c: &Call) -> bool {
+		matches!(
+			c,
+			Call::Balances(..) |
+				Call::Vesting(..) |
+				Call::Assets(..) |
+				Call::Uniques(..) |
+				Call::IndraIdentity(..) |
+				Call::Subscription(..)
+		)
+	}
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = BaseFee;
+	type GasWeightMapping = IndranetGasWeightMapping;
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressMapped<Self>;
+	type WithdrawOrigin = EnsureAddressMapped<Self>;
+	type AddressMapping = EvmAddressMapping<Self>;
+	type Currency = Balances;
+	type Event = Event;
+	type PrecompilesType = IndranetPrecompiles<Self, EvmDispatchCalls>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = ChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction =
+		EvmCurrencyAdapter<DealWithFees<Runtime, FeeTreasuryShare, FeeBurnShare>>;
+	type FindAuthor = FindAuthorTruncated<pallet_session::FindAccountFromAuthorIndex<Self, Aura>>;
+}
+
+impl pallet_evm_accounts::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type FallbackAddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type WeightInfo = pallet_evm_accounts::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_erc20_allowances::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type AssetId = AssetId;
+}
+
+impl pallet_ethereum::Config for Runtime {
+	type Event = Event;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot;
+}
+
+/// Bounds within which the base fee moves with block fullness. Since EVM transactions are normal
+/// extrinsics, a block is full once it uses `NORMAL_DISPATCH_RATIO` of its weight, and the base fee
+/// stays unchanged at half of that, as in EIP-1559.
+pub struct BaseFeeThreshold;
+impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
+	fn lower() -> Permill {
+		Permill::zero()
+	}
+	fn ideal() -> Permill {
+		Permill::from_percent(50)
+	}
+	fn upper() -> Permill {
+		Permill::from_parts(NORMAL_DISPATCH_RATIO.deconstruct() / 1_000)
+	}
+}
+
+/// We allow root and the Relay Chain council to tune the fee market.
+pub type FeeMarketUpdateOrigin = EnsureOneOf<
+	EnsureRoot<AccountId>,
+	EnsureXcm<IsMajorityOfBody<xcm_config::RelayLocation, ExecutiveBody>>,
+>;
+
+parameter_types! {
+	pub IsActive: bool = true;
+	pub DefaultBaseFeePerGas: U256 = U256::from(1_000_000_000);
+}
+
+impl pallet_base_fee::Config for Runtime {
+	type Event = Event;
+	type Threshold = BaseFeeThreshold;
+	type IsActive = IsActive;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type SetElasticityOrigin = FeeMarketUpdateOrigin;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -491,6 +409,10 @@ construct_runtime!(
 		// Monetary stuff.
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 10,
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 11,
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet} = 12,
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>} = 13,
+		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>} = 14,
+		FeeSplit: pallet_fee_split::{Pallet, Call, Storage, Event} = 15,
 
 		// Collator support. the order of these 5 are important and shall not change.
 		Authorship: pallet_authorship::{Pallet, Call, Storage} = 20,
@@ -511,14 +433,27 @@ construct_runtime!(
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>} = 42,
 
 		// Identity
-		IndraIdentity: pallet_studentid::{Pallet, Call, Storage, Event<T>} =50,
+		IndraIdentity: pallet_studentid::{Pallet, Call, Storage, Event<T>} = 50,
 		Subscription: pallet_subscription::{Pallet, Call, Storage, Event<T>} = 51,
 
+		// The main stage.
+		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>, Config<T>} = 52,
+		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>} = 53,
+
 		// Sudo.
 		Sudo: pallet_sudo::{Pallet, Call, Storage, Event<T>, Config<T>} = 60,
+
+		// Ethereum compatibility.
+		EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>} = 70,
+		Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin} = 71,
+		BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event} = 72,
+		EvmAccounts: pallet_evm_accounts::{Pallet, Call, Storage, Event<T>} = 73,
+		Erc20Allowances: pallet_erc20_allowances::{Pallet, Storage, Event<T>} = 74,
 	}
 );
 
+/// The identity record returned by the identity runtime API.
+pub type IdentityRegistration = pallet_studentid::RegistrationOf<Runtime>;
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block type as expected by this runtime.
@@ -536,12 +471,14 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_studentid::CheckVerifiedIdentity<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
-pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
+pub type UncheckedExtrinsic =
+	fp_self_contained::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
-pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+pub type CheckedExtrinsic = fp_self_contained::CheckedExtrinsic<AccountId, Call, SignedExtra, H160>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -549,8 +486,122 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (
+	pallet_studentid::migrations::v1::MigrateToV1<Runtime>,
+	InitStorageVersion<Runtime, Treasury>,
+	InitStorageVersion<Runtime, Vesting>,
+	InitStorageVersion<Runtime, FeeSplit>,
+	InitStorageVersion<Runtime, Assets>,
+	InitStorageVersion<Runtime, Uniques>,
+	InitStorageVersion<Runtime, EVM>,
+	InitStorageVersion<Runtime, Ethereum>,
+	InitStorageVersion<Runtime, BaseFee>,
+	InitStorageVersion<Runtime, EvmAccounts>,
+	InitStorageVersion<Runtime, Erc20Allowances>,
+);
+
+/// The asset `uxt` pays its fees in, if it does not pay them in the native currency.
+fn fee_asset(uxt: &UncheckedExtrinsic) -> Option<AssetId> {
+	let (_, _, extra) = uxt.0.signature.as_ref()?;
+	parachains_common::impls::fee_asset(&extra.7)
+}
+
+/// Wraps Ethereum transactions received over RPC into extrinsics.
+pub struct TransactionConverter;
+
+impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> UncheckedExtrinsic {
+		UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		)
+	}
+}
+
+impl fp_rpc::ConvertTransaction<opaque::UncheckedExtrinsic> for TransactionConverter {
+	fn convert_transaction(&self, transaction: EthereumTransaction) -> opaque::UncheckedExtrinsic {
+		let extrinsic = UncheckedExtrinsic::new_unsigned(
+			pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+		);
+		let encoded = extrinsic.encode();
+		opaque::UncheckedExtrinsic::decode(&mut &encoded[..])
+			.expect("Encoded extrinsic is always valid")
+	}
+}
+
+impl fp_self_contained::SelfContainedCall for Call {
+	type SignedInfo = H160;
+
+	fn is_self_contained(&self) -> bool {
+		match self {
+			Call::Ethereum(call) => call.is_self_contained(),
+			_ => false,
+		}
+	}
+
+	fn check_self_contained(&self) -> Option<Result<Self::SignedInfo, TransactionValidityError>> {
+		match self {
+			Call::Ethereum(call) => call.check_self_contained(),
+			_ => None,
+		}
+	}
+
+	fn validate_self_contained(&self, info: &Self::SignedInfo) -> Option<TransactionValidity> {
+		match self {
+			Call::Ethereum(call) => call.validate_self_contained(info),
+			_ => None,
+		}
+	}
+
+	fn pre_dispatch_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+	) -> Option<Result<(), TransactionValidityError>> {
+		match self {
+			Call::Ethereum(call) => call.pre_dispatch_self_contained(info),
+			_ => None,
+		}
+	}
+
+	fn apply_self_contained(
+		self,
+		info: Self::SignedInfo,
+	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
+		match self {
+			call @ Call::Ethereum(pallet_ethereum::Call::transact { .. }) => Some(
+				call.dispatch(Origin::from(pallet_ethereum::RawOrigin::EthereumTransaction(info))),
+			),
+			_ => None,
+		}
+	}
+}
+
+/// Apply the Ethereum transaction `uxt`, tracing its EVM execution with `config`.
+#[cfg(feature = "evm-tracing")]
+fn trace_ethereum_transaction(
+	uxt: UncheckedExtrinsic,
+	config: TracerConfig,
+) -> Result<TransactionTrace, DispatchError> {
+	let (result, mut trace) =
+		pallet_evm::tracing::trace(config, || Executive::apply_extrinsic(uxt));
+	result.map_err(|_| DispatchError::Other("invalid transaction"))?;
+
+	// The receipts record the gas used by the block so far, intrinsic cost and refund included.
+	let mut cumulative_gas =
+		Ethereum::pending().into_iter().rev().map(|(_, _, receipt)| match receipt {
+			pallet_ethereum::Receipt::Legacy(d) |
+			pallet_ethereum::Receipt::EIP2930(d) |
+			pallet_ethereum::Receipt::EIP1559(d) => d.used_gas,
+		});
+	let block_gas = cumulative_gas.next().unwrap_or_default();
+	let previous_gas = cumulative_gas.next().unwrap_or_default();
+	trace.set_gas_used(block_gas.saturating_sub(previous_gas));
+	Ok(trace)
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -560,12 +611,17 @@ mod benches {
 	define_benchmarks!(
 		[frame_system, SystemBench::<Runtime>]
 		[pallet_balances, Balances]
+		[pallet_assets, Assets]
+		[pallet_uniques, Uniques]
 		[pallet_multisig, Multisig]
 		[pallet_proxy, Proxy]
 		[pallet_session, SessionBench::<Runtime>]
 		[pallet_utility, Utility]
 		[pallet_timestamp, Timestamp]
 		[pallet_collator_selection, CollatorSelection]
+		[pallet_treasury, Treasury]
+		[pallet_vesting, Vesting]
+		[pallet_evm, EVM]
 	);
 }
 
@@ -660,13 +716,207 @@ impl_runtime_apis! {
 			uxt: <Block as BlockT>::Extrinsic,
 			len: u32,
 		) -> pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			let asset_id = fee_asset(&uxt);
+			AssetTxPaymentApi::<Runtime>::query_info(uxt, len, asset_id)
 		}
 		fn query_fee_details(
 			uxt: <Block as BlockT>::Extrinsic,
 			len: u32,
 		) -> pallet_transaction_payment::FeeDetails<Balance> {
-			TransactionPayment::query_fee_details(uxt, len)
+			let asset_id = fee_asset(&uxt);
+			AssetTxPaymentApi::<Runtime>::query_fee_details(uxt, len, asset_id)
+		}
+	}
+
+	impl pallet_studentid_rpc_runtime_api::IdentityApi<Block, AccountId, IdentityRegistration> for Runtime {
+		fn identity_of(who: AccountId) -> Option<IdentityRegistration> {
+			IndraIdentity::identity_of(&who)
+		}
+		fn account_of(email_hash: sp_core::H256) -> Option<AccountId> {
+			IndraIdentity::account_of(email_hash.as_fixed_bytes())
+		}
+		fn has_service_access(who: AccountId, service: Vec<u8>) -> bool {
+			service.try_into().map_or(false, |service| {
+				IndraIdentity::has_service_access(&who, &pallet_studentid::Data::Raw(service))
+			})
+		}
+		fn judgements_of(who: AccountId) -> Vec<(pallet_studentid::RegistrarIndex, pallet_studentid::Judgement)> {
+			IndraIdentity::judgements_of(&who)
+		}
+	}
+
+	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> EVMAccount {
+			EVM::account_basic(&address)
+		}
+
+		fn gas_price() -> U256 {
+			<Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price()
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			EVM::account_codes(address)
+		}
+
+		fn author() -> H160 {
+			<pallet_evm::Pallet<Runtime>>::find_author()
+		}
+
+		fn storage_at(address: H160, index: U256) -> H256 {
+			let mut tmp = [0u8; 32];
+			index.to_big_endian(&mut tmp);
+			EVM::account_storages(address, H256::from_slice(&tmp[..]))
+		}
+
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			<Runtime as pallet_evm::Config>::Runner::call(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.low_u64(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.into())
+		}
+
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			<Runtime as pallet_evm::Config>::Runner::create(
+				from,
+				data,
+				value,
+				gas_limit.low_u64(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			).map_err(|err| err.into())
+		}
+
+		fn current_transaction_statuses() -> Option<Vec<fp_rpc::TransactionStatus>> {
+			Ethereum::current_transaction_statuses()
+		}
+
+		fn current_block() -> Option<pallet_ethereum::Block> {
+			Ethereum::current_block()
+		}
+
+		fn current_receipts() -> Option<Vec<pallet_ethereum::Receipt>> {
+			Ethereum::current_receipts()
+		}
+
+		fn current_all() -> (
+			Option<pallet_ethereum::Block>,
+			Option<Vec<pallet_ethereum::Receipt>>,
+			Option<Vec<fp_rpc::TransactionStatus>>,
+		) {
+			(
+				Ethereum::current_block(),
+				Ethereum::current_receipts(),
+				Ethereum::current_transaction_statuses(),
+			)
+		}
+
+		fn extrinsic_filter(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<EthereumTransaction> {
+			xts.into_iter().filter_map(|xt| match xt.0.function {
+				Call::Ethereum(transact { transaction }) => Some(transaction),
+				_ => None,
+			}).collect::<Vec<EthereumTransaction>>()
+		}
+
+		fn elasticity() -> Option<Permill> {
+			Some(BaseFee::elasticity())
+		}
+	}
+
+	#[cfg(feature = "evm-tracing")]
+	impl fp_rpc::DebugRuntimeApi<Block> for Runtime {
+		fn trace_transaction(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			config: TracerConfig,
+		) -> Result<TransactionTrace, DispatchError> {
+			for ext in extrinsics {
+				let traced = match &ext.0.function {
+					Call::Ethereum(transact { transaction }) => transaction.hash() == transaction_hash,
+					_ => false,
+				};
+				if traced {
+					return trace_ethereum_transaction(ext, config);
+				}
+				let _ = Executive::apply_extrinsic(ext);
+			}
+			Err(DispatchError::Other("transaction not found in the block"))
+		}
+
+		fn trace_block(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			config: TracerConfig,
+		) -> Result<Vec<TransactionTrace>, DispatchError> {
+			let mut traces = Vec::new();
+			for ext in extrinsics {
+				if matches!(ext.0.function, Call::Ethereum(transact { .. })) {
+					traces.push(trace_ethereum_transaction(ext, config)?);
+				} else {
+					let _ = Executive::apply_extrinsic(ext);
+				}
+			}
+			Ok(traces)
+		}
+	}
+
+	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+			)
 		}
 	}
 
@@ -769,3 +1019,356 @@ cumulus_pallet_parachain_system::register_validate_block! {
 	BlockExecutor = cumulus_pallet_aura_ext::BlockExecutor::<Runtime, Executive>,
 	CheckInherents = CheckInherents,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		traits::{InstanceFilter, OnFinalize},
+		weights::{DispatchClass, DispatchInfo, PostDispatchInfo, WeightToFeePolynomial},
+	};
+	use pallet_evm::AddressMapping;
+	use pallet_transaction_payment::OnChargeTransaction;
+	use sp_runtime::AccountId32;
+	use xcm::latest::prelude::*;
+
+	const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+	const EVM_ALICE: H160 = H160::repeat_byte(1);
+
+	fn remark() -> Call {
+		Call::System(frame_system::Call::remark { remark: vec![] })
+	}
+
+	fn batch(call: Call) -> Call {
+		Call::Utility(pallet_utility::Call::batch { calls: vec![call] })
+	}
+
+	fn balances_transfer() -> Call {
+		Call::Balances(pallet_balances::Call::transfer { dest: ALICE.into(), value: 1 })
+	}
+
+	fn vested_transfer() -> Call {
+		Call::Vesting(pallet_vesting::Call::vested_transfer {
+			target: ALICE.into(),
+			schedule: pallet_vesting::VestingInfo::new(10 * UNITS, UNITS, 0),
+		})
+	}
+
+	fn vest() -> Call {
+		Call::Vesting(pallet_vesting::Call::vest {})
+	}
+
+	fn evm_withdraw() -> Call {
+		Call::EVM(pallet_evm::Call::withdraw { address: H160::repeat_byte(1), value: 1 })
+	}
+
+	fn claim_evm_account() -> Call {
+		Call::EvmAccounts(pallet_evm_accounts::Call::claim_account {
+			evm_address: EVM_ALICE,
+			signature: sp_core::ecdsa::Signature::from_raw([0u8; 65]),
+		})
+	}
+
+	fn assets_transfer() -> Call {
+		Call::Assets(pallet_assets::Call::transfer { id: 1, target: ALICE.into(), amount: 1 })
+	}
+
+	fn uniques_transfer() -> Call {
+		Call::Uniques(pallet_uniques::Call::transfer { class: 1, instance: 1, dest: ALICE.into() })
+	}
+
+	fn xcm_teleport() -> Call {
+		Call::SelendraXcm(pallet_xcm::Call::teleport_assets {
+			dest: Box::new(MultiLocation::parent().into()),
+			beneficiary: Box::new(MultiLocation::here().into()),
+			assets: Box::new(MultiAssets::from(MultiAsset::from((Parent, 1u128))).into()),
+			fee_asset_item: 0,
+		})
+	}
+
+	fn subscribe_service() -> Call {
+		Call::Subscription(pallet_subscription::Call::subscribe_service { service_id: 0 })
+	}
+
+	fn request_approved_publisher() -> Call {
+		Call::Subscription(pallet_subscription::Call::request_approved_publisher {})
+	}
+
+	fn identity_login() -> Call {
+		Call::IndraIdentity(pallet_studentid::Call::login_web3_sel16 { email: b"a@b.c".to_vec() })
+	}
+
+	fn register_as_candidate() -> Call {
+		Call::CollatorSelection(pallet_collator_selection::Call::register_as_candidate {})
+	}
+
+	fn reject_announcement() -> Call {
+		Call::Proxy(pallet_proxy::Call::reject_announcement {
+			delegate: ALICE,
+			call_hash: Default::default(),
+		})
+	}
+
+	fn governance_calls() -> Vec<Call> {
+		vec![
+			Call::Treasury(pallet_treasury::Call::propose_spend {
+				value: 1,
+				beneficiary: ALICE.into(),
+			}),
+			Call::FeeSplit(pallet_fee_split::Call::set_fee_split {
+				treasury_share: Default::default(),
+				burn_share: Default::default(),
+			}),
+		]
+	}
+
+	fn value_moving_calls() -> Vec<Call> {
+		vec![
+			balances_transfer(),
+			vested_transfer(),
+			evm_withdraw(),
+			claim_evm_account(),
+			assets_transfer(),
+			uniques_transfer(),
+			xcm_teleport(),
+			subscribe_service(),
+		]
+	}
+
+	#[test]
+	fn any_proxy_allows_everything() {
+		for call in value_moving_calls().into_iter().chain(governance_calls()) {
+			assert!(ProxyType::Any.filter(&call));
+		}
+		assert!(ProxyType::Any.filter(&remark()));
+	}
+
+	#[test]
+	fn non_transfer_proxy_excludes_value_moving_calls() {
+		for call in value_moving_calls() {
+			assert!(!ProxyType::NonTransfer.filter(&call));
+		}
+		for call in governance_calls() {
+			assert!(ProxyType::NonTransfer.filter(&call));
+		}
+		assert!(ProxyType::NonTransfer.filter(&remark()));
+		assert!(ProxyType::NonTransfer.filter(&vest()));
+		assert!(ProxyType::NonTransfer.filter(&request_approved_publisher()));
+		assert!(ProxyType::NonTransfer.filter(&identity_login()));
+		assert!(ProxyType::NonTransfer.filter(&register_as_candidate()));
+	}
+
+	#[test]
+	fn cancel_proxy_only_rejects_announcements() {
+		assert!(ProxyType::CancelProxy.filter(&reject_announcement()));
+		assert!(ProxyType::CancelProxy.filter(&batch(reject_announcement())));
+		assert!(!ProxyType::CancelProxy.filter(&remark()));
+		assert!(!ProxyType::CancelProxy.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn collator_proxy_is_scoped_to_collator_selection() {
+		assert!(ProxyType::Collator.filter(&register_as_candidate()));
+		assert!(!ProxyType::Collator.filter(&remark()));
+		assert!(!ProxyType::Collator.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn assets_proxy_is_scoped_to_assets_and_uniques() {
+		assert!(ProxyType::Assets.filter(&assets_transfer()));
+		assert!(ProxyType::Assets.filter(&uniques_transfer()));
+		assert!(!ProxyType::Assets.filter(&balances_transfer()));
+		assert!(!ProxyType::Assets.filter(&xcm_teleport()));
+	}
+
+	#[test]
+	fn subscription_proxy_is_scoped_to_subscription() {
+		assert!(ProxyType::Subscription.filter(&subscribe_service()));
+		assert!(ProxyType::Subscription.filter(&request_approved_publisher()));
+		assert!(!ProxyType::Subscription.filter(&balances_transfer()));
+		assert!(!ProxyType::Subscription.filter(&identity_login()));
+	}
+
+	#[test]
+	fn identity_proxy_is_scoped_to_identity() {
+		assert!(ProxyType::Identity.filter(&identity_login()));
+		assert!(!ProxyType::Identity.filter(&subscribe_service()));
+		assert!(!ProxyType::Identity.filter(&balances_transfer()));
+	}
+
+	#[test]
+	fn governance_proxy_is_scoped_to_governance() {
+		for call in governance_calls() {
+			assert!(ProxyType::Governance.filter(&call));
+		}
+		for call in value_moving_calls() {
+			assert!(!ProxyType::Governance.filter(&call));
+		}
+		assert!(!ProxyType::Governance.filter(&identity_login()));
+	}
+
+	#[test]
+	fn proxy_supersets() {
+		assert!(ProxyType::Any.is_superset(&ProxyType::Governance));
+		assert!(!ProxyType::Governance.is_superset(&ProxyType::Any));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Identity));
+		assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Subscription));
+		assert!(!ProxyType::NonTransfer.is_superset(&ProxyType::Assets));
+		assert!(!ProxyType::Subscription.is_superset(&ProxyType::Identity));
+	}
+
+	fn base_fee_after_block_of(weight: Weight) -> U256 {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			System::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+			BaseFee::on_finalize(System::block_number());
+			BaseFee::base_fee_per_gas()
+		})
+	}
+
+	#[test]
+	fn base_fee_targets_half_of_normal_dispatch() {
+		let full = NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT;
+		assert_eq!(DefaultBaseFeePerGas::get(), U256::from(1_000_000_000));
+
+		assert_eq!(base_fee_after_block_of(full / 2), U256::from(1_000_000_000));
+		assert_eq!(base_fee_after_block_of(full), U256::from(1_125_000_000));
+		assert_eq!(base_fee_after_block_of(MAXIMUM_BLOCK_WEIGHT), U256::from(1_125_000_000));
+		assert_eq!(base_fee_after_block_of(0), U256::from(875_000_000));
+	}
+
+	#[test]
+	fn elasticity_is_set_by_governance() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			assert!(BaseFee::set_elasticity(Origin::signed(ALICE), Permill::zero()).is_err());
+			assert!(BaseFee::set_elasticity(Origin::root(), Permill::zero()).is_ok());
+			assert_eq!(BaseFee::elasticity(), Permill::zero());
+		});
+	}
+
+	/// A signed `remark()` paying its fees in `asset_id`.
+	fn signed_remark(asset_id: Option<AssetId>) -> UncheckedExtrinsic {
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(generic::Era::Immortal),
+			frame_system::CheckNonce::from(0),
+			frame_system::CheckWeight::new(),
+			pallet_asset_tx_payment::ChargeAssetTxPayment::from(0, asset_id),
+			pallet_studentid::CheckVerifiedIdentity::new(),
+		);
+		let signature = sp_core::sr25519::Signature::from_raw([0; 64]);
+		UncheckedExtrinsic::new_signed(remark(), ALICE.into(), signature.into(), extra)
+	}
+
+	#[test]
+	fn fee_asset_is_read_from_the_signed_extra() {
+		assert_eq!(fee_asset(&signed_remark(Some(7))), Some(7));
+		assert_eq!(fee_asset(&signed_remark(None)), None);
+		assert_eq!(fee_asset(&UncheckedExtrinsic::new_unsigned(remark())), None);
+	}
+
+	#[test]
+	fn fees_in_assets_that_cannot_pay_them_are_quoted_natively() {
+		sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+			// Only sufficient assets pay fees.
+			assert!(Assets::force_create(Origin::root(), 7, ALICE.into(), false, 1).is_ok());
+			assert_eq!(AssetTxPaymentApi::<Runtime>::fee_in_asset(UNITS, 7), None);
+
+			let xt = signed_remark(Some(7));
+			let len = xt.encode().len() as u32;
+			assert_eq!(
+				AssetTxPaymentApi::<Runtime>::query_info(xt.clone(), len, Some(7)),
+				AssetTxPaymentApi::<Runtime>::query_info(xt.clone(), len, None),
+			);
+			assert_eq!(
+				AssetTxPaymentApi::<Runtime>::query_fee_details(xt.clone(), len, Some(7)),
+				AssetTxPaymentApi::<Runtime>::query_fee_details(xt, len, None),
+			);
+		});
+	}
+
+	type ExtrinsicCharger = <Runtime as pallet_transaction_payment::Config>::OnChargeTransaction;
+	type EvmCharger = <Runtime as pallet_evm::Config>::OnChargeTransaction;
+
+	/// The balances of the treasury, the staking pot and `payer`, and the total issuance, after
+	/// `charge` charged `payer` its fees.
+	fn fee_split(payer: &AccountId, charge: impl FnOnce()) -> [Balance; 4] {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(payer.clone(), 100 * UNITS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			charge();
+			[
+				Balances::free_balance(Treasury::account_id()),
+				Balances::free_balance(CollatorSelection::account_id()),
+				Balances::free_balance(payer),
+				Balances::total_issuance(),
+			]
+		})
+	}
+
+	/// Charge an extrinsic of `weight`, with `tip`, the way `TransactionPayment` does.
+	fn charge_extrinsic(weight: Weight, tip: Balance) -> [Balance; 4] {
+		let info = DispatchInfo { weight, ..Default::default() };
+		let fee = WeightToFee::calc(&weight) + tip;
+		fee_split(&ALICE, || {
+			let paid = <ExtrinsicCharger as OnChargeTransaction<Runtime>>::withdraw_fee(
+				&ALICE,
+				&remark(),
+				&info,
+				fee,
+				tip,
+			)
+			.unwrap();
+			<ExtrinsicCharger as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+				&ALICE,
+				&info,
+				&PostDispatchInfo::default(),
+				fee,
+				tip,
+				paid,
+			)
+			.unwrap();
+		})
+	}
+
+	/// Charge an EVM transaction using the gas equivalent to `weight`, with a base fee pricing it
+	/// like an extrinsic and a priority fee of `tip`, the way `pallet_evm`'s runner does.
+	fn charge_evm_transaction(weight: Weight, tip: Balance) -> [Balance; 4] {
+		let base_fee = U256::from(WeightToFee::calc(&weight));
+		let fee = base_fee + tip;
+		let payer = <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(EVM_ALICE);
+		fee_split(&payer, || {
+			// The runner withdraws the maximal fee, and refunds what's left once executed.
+			let paid = EvmCharger::withdraw_fee(&EVM_ALICE, fee * 2).unwrap();
+			let tip = EvmCharger::correct_and_deposit_fee(&EVM_ALICE, fee, base_fee, paid);
+			EvmCharger::pay_priority_fee(tip);
+		})
+	}
+
+	#[test]
+	fn evm_fees_are_split_like_extrinsic_fees() {
+		for gas in [21_000, 1_000_000, 15_000_000] {
+			let weight = IndranetGasWeightMapping::gas_to_weight(gas);
+			for tip in [0, CENTS, UNITS] {
+				assert_eq!(charge_evm_transaction(weight, tip), charge_extrinsic(weight, tip));
+			}
+		}
+	}
+
+	#[test]
+	fn evm_fees_reach_treasury_and_collators() {
+		let weight = IndranetGasWeightMapping::gas_to_weight(15_000_000);
+		let fee = WeightToFee::calc(&weight);
+		let [treasury, pot, payer, issuance] = charge_evm_transaction(weight, UNITS);
+
+		assert_eq!(treasury, FeeTreasuryShare::get().mul_floor(fee));
+		assert_eq!(pot, fee - treasury - FeeBurnShare::get().mul_floor(fee) + UNITS);
+		assert_eq!(payer, 100 * UNITS - fee - UNITS);
+		assert_eq!(issuance, 100 * UNITS - FeeBurnShare::get().mul_floor(fee));
+	}
+}
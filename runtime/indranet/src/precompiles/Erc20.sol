@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.0;
+
+/// @title The interface of the ERC-20 precompiles.
+/// @dev SEL is deployed at 0x0000000000000000000000000000000000000801, and each asset at
+/// 0xffffffff000000000000000000000000 followed by its id as 4 big-endian bytes.
+interface Erc20 {
+	/// @dev Emitted when `value` tokens are moved from `from` to `to`.
+	event Transfer(address indexed from, address indexed to, uint256 value);
+
+	/// @dev Emitted when `owner` allows `spender` to transfer `value` of its tokens.
+	event Approval(address indexed owner, address indexed spender, uint256 value);
+
+	function name() external view returns (string memory);
+
+	function symbol() external view returns (string memory);
+
+	function decimals() external view returns (uint8);
+
+	function totalSupply() external view returns (uint256);
+
+	function balanceOf(address who) external view returns (uint256);
+
+	function allowance(address owner, address spender) external view returns (uint256);
+
+	/// @dev Transfer `value` tokens from the caller to `to`.
+	function transfer(address to, uint256 value) external returns (bool);
+
+	/// @dev Allow `spender` to transfer `value` of the caller's tokens, replacing the previous
+	/// allowance. Values above 2^128 - 1 are saturated.
+	function approve(address spender, uint256 value) external returns (bool);
+
+	/// @dev Transfer `value` tokens from `from` to `to`, spending the caller's allowance.
+	function transferFrom(address from, address to, uint256 value) external returns (bool);
+}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.0;
+
+/// @title The interface of the subscription precompile.
+/// @dev Deployed at 0x0000000000000000000000000000000000000800.
+interface Subscription {
+	/// @dev Emitted when the caller published the service `serviceId`.
+	event ServicePublished(uint32 indexed serviceId, address indexed publisher);
+
+	/// @dev Emitted when the caller subscribed to the service `serviceId`.
+	event ServiceSubscribed(uint32 indexed serviceId, address indexed subscriber);
+
+	/// @dev Emitted when the caller cancelled its subscription to the service `serviceId`.
+	event ServiceUnsubscribed(uint32 indexed serviceId, address indexed subscriber);
+
+	/// @dev Emitted when the caller renewed its subscription to the service `serviceId`.
+	event SubscriptionRenewed(
+		uint32 indexed serviceId,
+		address indexed subscriber,
+		uint256 expireOn
+	);
+
+	/// @dev Publish a service costing `cost` to subscribe to, renewed every `period` blocks,
+	/// or never expiring if `period` is zero. The caller must be an approved publisher.
+	/// @return serviceId The index of the new service.
+	function publishService(
+		uint256 cost,
+		string calldata name,
+		string calldata description,
+		uint32 period
+	) external returns (uint32 serviceId);
+
+	/// @dev Subscribe to the service `serviceId`, paying its cost to the publisher.
+	function subscribe(uint32 serviceId) external;
+
+	/// @dev Cancel the subscription to the service `serviceId`.
+	function unsubscribe(uint32 serviceId) external;
+
+	/// @dev Renew the subscription to the periodic service `serviceId` from the current block.
+	/// @return expireOn The block the subscription now expires on.
+	function renew(uint32 serviceId) external returns (uint256 expireOn);
+
+	/// @dev Whether `who` holds a valid subscription to the service `serviceId`.
+	function isSubscribed(uint32 serviceId, address who) external view returns (bool);
+}
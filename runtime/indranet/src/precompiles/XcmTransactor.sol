@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.0;
+
+/// @title The interface of the XCM transactor precompile.
+/// @dev Deployed at 0x0000000000000000000000000000000000000803. A `paraId` of 0 is the relay
+/// chain, and any other one a sibling parachain.
+interface XcmTransactor {
+	/// @dev Reserve transfer `amount` of the caller's SEL to the account `beneficiary` of
+	/// `paraId`.
+	function reserveTransfer(uint32 paraId, bytes32 beneficiary, uint256 amount) external;
+
+	/// @dev Teleport `amount` of the caller's SEL to the account `beneficiary` of `paraId`.
+	function teleport(uint32 paraId, bytes32 beneficiary, uint256 amount) external;
+}
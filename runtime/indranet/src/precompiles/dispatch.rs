@@ -0,0 +1,235 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile dispatching a SCALE encoded runtime call as the caller, in place of Frontier's
+//! `pallet-evm-precompile-dispatch`.
+//!
+//! Only the calls allowed by both `F` and `frame_system::Config::BaseCallFilter` are dispatched,
+//! and never from within a dispatch made by this precompile. A failed dispatch reverts with the
+//! SCALE encoded `DispatchError` as its data.
+
+use super::utils::{EvmDataWriter, Gasometer};
+use codec::{Decode, DecodeLimit, Encode};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	storage::unhashed,
+	traits::Contains,
+	weights::{DispatchClass, Pays},
+};
+use pallet_evm::{
+	AddressMapping, Context, ExitRevert, Precompile, PrecompileFailure, PrecompileResult,
+};
+use sp_std::{marker::PhantomData, vec};
+
+/// The storage key flagging a dispatch in progress, which is removed once it returns.
+const DISPATCHING_KEY: &[u8] = b":indranet:evm:dispatching";
+
+/// How deeply the dispatched call may nest calls.
+const CALL_DECODE_DEPTH_LIMIT: u32 = 8;
+
+/// The dispatch precompile, for the calls allowed by `F`.
+pub struct DispatchPrecompile<R, F>(PhantomData<(R, F)>);
+
+impl<R, F> Precompile for DispatchPrecompile<R, F>
+where
+	R: pallet_evm::Config,
+	R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	F: Contains<R::Call>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::<R>::new(target_gas);
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+
+		if unhashed::exists(DISPATCHING_KEY) {
+			return Err(gasometer.revert("nested dispatch is forbidden"))
+		}
+
+		let call = R::Call::decode_with_depth_limit(CALL_DECODE_DEPTH_LIMIT, &mut &*input)
+			.map_err(|_| gasometer.revert("failed to decode call"))?;
+		if !F::contains(&call) || !<R as frame_system::Config>::BaseCallFilter::contains(&call) {
+			return Err(gasometer.revert("call is not allowed"))
+		}
+
+		let info = call.get_dispatch_info();
+		if info.pays_fee != Pays::Yes || info.class != DispatchClass::Normal {
+			return Err(gasometer.revert("call is not allowed"))
+		}
+		gasometer.record_weight(info.weight)?;
+
+		let origin = R::AddressMapping::into_account_id(context.caller);
+		unhashed::put(DISPATCHING_KEY, &true);
+		let result = call.dispatch(Some(origin).into());
+		unhashed::kill(DISPATCHING_KEY);
+
+		match result {
+			Ok(post_info) => {
+				gasometer
+					.refund_weight(info.weight, post_info.actual_weight.unwrap_or(info.weight));
+				Ok(gasometer.succeed(EvmDataWriter::new().build(), vec![]))
+			},
+			Err(e) => {
+				gasometer
+					.refund_weight(info.weight, e.post_info.actual_weight.unwrap_or(info.weight));
+				Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output: e.error.encode(),
+					cost: gasometer.used(),
+				})
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::utils::revert, AccountId, Balances, Call, EvmDispatchCalls, Runtime, System,
+		UNITS,
+	};
+	use sp_core::{H160, U256};
+	use sp_runtime::DispatchError;
+
+	type Dispatch = DispatchPrecompile<Runtime, EvmDispatchCalls>;
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+
+	fn account(address: H160) -> AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(account(ALICE), 100 * UNITS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn dispatch(call: Call) -> PrecompileResult {
+		let context = Context {
+			address: H160::from_low_u64_be(2050),
+			caller: ALICE,
+			apparent_value: U256::zero(),
+		};
+		Dispatch::execute(&call.encode(), None, &context, false)
+	}
+
+	fn transfer(value: u128) -> Call {
+		Call::Balances(pallet_balances::Call::transfer {
+			dest: AccountId::new([2u8; 32]).into(),
+			value,
+		})
+	}
+
+	fn revert_data(result: PrecompileResult) -> Vec<u8> {
+		match result {
+			Err(PrecompileFailure::Revert { output, .. }) => output,
+			_ => panic!("expected a revert"),
+		}
+	}
+
+	#[test]
+	fn allowed_call_is_dispatched_as_caller() {
+		new_test_ext().execute_with(|| {
+			let output = dispatch(transfer(UNITS)).unwrap();
+
+			assert!(output.cost > 0);
+			assert_eq!(Balances::free_balance(AccountId::new([2u8; 32])), UNITS);
+			assert!(!unhashed::exists(DISPATCHING_KEY));
+		});
+	}
+
+	#[test]
+	fn failed_dispatch_reverts_with_dispatch_error() {
+		new_test_ext().execute_with(|| {
+			let data = revert_data(dispatch(transfer(1_000 * UNITS)));
+
+			assert_eq!(
+				DispatchError::decode(&mut &data[..]).unwrap(),
+				pallet_balances::Error::<Runtime>::InsufficientBalance.into()
+			);
+			assert!(!unhashed::exists(DISPATCHING_KEY));
+		});
+	}
+
+	#[test]
+	fn calls_outside_the_allow_list_are_rejected() {
+		new_test_ext().execute_with(|| {
+			let sudo = Call::Sudo(pallet_sudo::Call::sudo { call: Box::new(transfer(UNITS)) });
+			let batch = Call::Utility(pallet_utility::Call::batch { calls: vec![transfer(UNITS)] });
+
+			for call in [sudo, batch] {
+				assert_eq!(
+					revert_data(dispatch(call)),
+					revert_data(Err(revert("call is not allowed", 0)))
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn calls_requiring_an_identity_check_the_caller() {
+		new_test_ext().execute_with(|| {
+			let publish = Call::Subscription(pallet_subscription::Call::publish_service {
+				cost: UNITS,
+				name: b"news".to_vec(),
+				description: vec![],
+				maybe_periodic: None,
+			});
+
+			let data = revert_data(dispatch(publish));
+			assert_eq!(DispatchError::decode(&mut &data[..]).unwrap(), DispatchError::BadOrigin);
+		});
+	}
+
+	#[test]
+	fn invalid_input_is_rejected() {
+		new_test_ext().execute_with(|| {
+			let context = Context {
+				address: H160::from_low_u64_be(2050),
+				caller: ALICE,
+				apparent_value: U256::zero(),
+			};
+
+			assert_eq!(
+				revert_data(Dispatch::execute(&[0xff, 0xff], None, &context, false)),
+				revert_data(Err(revert("failed to decode call", 0)))
+			);
+		});
+	}
+
+	#[test]
+	fn nested_dispatch_is_forbidden() {
+		new_test_ext().execute_with(|| {
+			unhashed::put(DISPATCHING_KEY, &true);
+
+			assert_eq!(
+				revert_data(dispatch(transfer(UNITS))),
+				revert_data(Err(revert("nested dispatch is forbidden", 0)))
+			);
+			assert_eq!(Balances::free_balance(AccountId::new([2u8; 32])), 0);
+		});
+	}
+}
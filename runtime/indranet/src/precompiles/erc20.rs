@@ -0,0 +1,602 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ERC-20 precompiles for the native currency and for each asset, with the interface in
+//! `Erc20.sol`.
+//!
+//! The native currency is exposed at a fixed address, and each asset at [`asset_address`] of its
+//! id. Allowances are kept by `pallet_erc20_allowances`, amounts above `u128::MAX` are saturated.
+
+use super::utils::{
+	address_topic, event_topic, log, record_logs, selector, EvmDataReader, EvmDataWriter, Gasometer,
+};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{
+		tokens::fungibles::{self, metadata::Inspect as _, Inspect as _, Transfer as _},
+		Currency,
+		ExistenceRequirement::AllowDeath,
+	},
+	weights::Weight,
+};
+use pallet_assets::WeightInfo as _;
+use pallet_balances::WeightInfo as _;
+use pallet_erc20_allowances::Token;
+use pallet_evm::{AddressMapping, Context, Precompile, PrecompileResult, PrecompileSet};
+use parachains_common::AssetId;
+use sp_core::{H160, U256};
+use sp_runtime::traits::Zero;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+/// The first bytes of the address of an asset's ERC-20, followed by zeros and the asset id.
+const ASSET_ADDRESS_PREFIX: [u8; 4] = [0xff; 4];
+
+/// The address of the ERC-20 of asset `id`.
+pub fn asset_address(id: AssetId) -> H160 {
+	let mut address = H160::zero();
+	address[..4].copy_from_slice(&ASSET_ADDRESS_PREFIX);
+	address[16..].copy_from_slice(&id.to_be_bytes());
+	address
+}
+
+/// The asset whose ERC-20 is at `address`, if it has the shape of an asset address.
+fn address_asset(address: H160) -> Option<AssetId> {
+	if address[..4] != ASSET_ADDRESS_PREFIX || address[4..16].iter().any(|b| *b != 0) {
+		return None;
+	}
+
+	let mut id = [0u8; 4];
+	id.copy_from_slice(&address[16..]);
+	Some(AssetId::from_be_bytes(id))
+}
+
+/// The name, symbol and decimals of the native currency.
+pub trait NativeMetadata {
+	fn name() -> &'static str;
+	fn symbol() -> &'static str;
+	fn decimals() -> u8;
+}
+
+/// The balances behind an ERC-20 precompile.
+trait Erc20Token<R: pallet_evm::Config> {
+	fn token(&self) -> Token<AssetId>;
+	fn name(&self) -> Vec<u8>;
+	fn symbol(&self) -> Vec<u8>;
+	fn decimals(&self) -> u8;
+	fn total_supply(&self) -> u128;
+	fn balance_of(&self, who: &R::AccountId) -> u128;
+	fn transfer(&self, from: &R::AccountId, to: &R::AccountId, amount: u128) -> DispatchResult;
+	fn transfer_weight(&self) -> Weight;
+}
+
+/// The native currency, held in `pallet_balances`.
+struct Native<M>(PhantomData<M>);
+
+impl<R, M> Erc20Token<R> for Native<M>
+where
+	R: pallet_evm::Config + pallet_balances::Config<Balance = u128>,
+	M: NativeMetadata,
+{
+	fn token(&self) -> Token<AssetId> {
+		Token::Native
+	}
+
+	fn name(&self) -> Vec<u8> {
+		M::name().as_bytes().to_vec()
+	}
+
+	fn symbol(&self) -> Vec<u8> {
+		M::symbol().as_bytes().to_vec()
+	}
+
+	fn decimals(&self) -> u8 {
+		M::decimals()
+	}
+
+	fn total_supply(&self) -> u128 {
+		pallet_balances::Pallet::<R>::total_issuance()
+	}
+
+	fn balance_of(&self, who: &R::AccountId) -> u128 {
+		pallet_balances::Pallet::<R>::free_balance(who)
+	}
+
+	fn transfer(&self, from: &R::AccountId, to: &R::AccountId, amount: u128) -> DispatchResult {
+		<pallet_balances::Pallet<R> as Currency<_>>::transfer(from, to, amount, AllowDeath)
+	}
+
+	fn transfer_weight(&self) -> Weight {
+		<R as pallet_balances::Config>::WeightInfo::transfer()
+	}
+}
+
+/// An asset of `pallet_assets`.
+struct Asset(AssetId);
+
+impl<R> Erc20Token<R> for Asset
+where
+	R: pallet_evm::Config + pallet_assets::Config<AssetId = AssetId, Balance = u128>,
+{
+	fn token(&self) -> Token<AssetId> {
+		Token::Asset(self.0)
+	}
+
+	fn name(&self) -> Vec<u8> {
+		pallet_assets::Pallet::<R>::name(&self.0)
+	}
+
+	fn symbol(&self) -> Vec<u8> {
+		pallet_assets::Pallet::<R>::symbol(&self.0)
+	}
+
+	fn decimals(&self) -> u8 {
+		<pallet_assets::Pallet<R> as fungibles::metadata::Inspect<_>>::decimals(&self.0)
+	}
+
+	fn total_supply(&self) -> u128 {
+		pallet_assets::Pallet::<R>::total_issuance(self.0)
+	}
+
+	fn balance_of(&self, who: &R::AccountId) -> u128 {
+		<pallet_assets::Pallet<R> as fungibles::Inspect<_>>::balance(self.0, who)
+	}
+
+	fn transfer(&self, from: &R::AccountId, to: &R::AccountId, amount: u128) -> DispatchResult {
+		<pallet_assets::Pallet<R> as fungibles::Transfer<_>>::transfer(
+			self.0, from, to, amount, false,
+		)
+		.map(|_| ())
+	}
+
+	fn transfer_weight(&self) -> Weight {
+		<R as pallet_assets::Config>::WeightInfo::transfer()
+	}
+}
+
+/// The ERC-20 functions, over the balances of a token.
+struct Erc20<R, T> {
+	token: T,
+	_marker: PhantomData<R>,
+}
+
+impl<R, T> Erc20<R, T>
+where
+	R: pallet_evm::Config + pallet_erc20_allowances::Config<AssetId = AssetId, Balance = u128>,
+	T: Erc20Token<R>,
+{
+	fn execute(
+		self,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::<R>::new(target_gas);
+		let (selector, mut reader) = EvmDataReader::new_with_selector(input)?;
+
+		let output = match selector {
+			s if s == self::selector("name()") => {
+				gasometer.record_reads(1)?;
+				EvmDataWriter::new().write_bytes(&self.token.name())
+			},
+			s if s == self::selector("symbol()") => {
+				gasometer.record_reads(1)?;
+				EvmDataWriter::new().write_bytes(&self.token.symbol())
+			},
+			s if s == self::selector("decimals()") => {
+				gasometer.record_reads(1)?;
+				EvmDataWriter::new().write_u256(self.token.decimals())
+			},
+			s if s == self::selector("totalSupply()") => {
+				gasometer.record_reads(1)?;
+				EvmDataWriter::new().write_u256(self.token.total_supply())
+			},
+			s if s == self::selector("balanceOf(address)") => {
+				reader.expect_arguments(1)?;
+				let who = R::AddressMapping::into_account_id(reader.read_address()?);
+
+				gasometer.record_reads(1)?;
+				EvmDataWriter::new().write_u256(self.token.balance_of(&who))
+			},
+			s if s == self::selector("allowance(address,address)") => {
+				reader.expect_arguments(2)?;
+				let owner = R::AddressMapping::into_account_id(reader.read_address()?);
+				let spender = R::AddressMapping::into_account_id(reader.read_address()?);
+
+				gasometer.record_reads(1)?;
+				let allowance = pallet_erc20_allowances::Pallet::<R>::allowance(
+					self.token.token(),
+					&owner,
+					&spender,
+				);
+				EvmDataWriter::new().write_u256(allowance)
+			},
+			s if s == self::selector("transfer(address,uint256)") =>
+				return self.transfer(gasometer, reader, context, is_static),
+			s if s == self::selector("approve(address,uint256)") =>
+				return self.approve(gasometer, reader, context, is_static),
+			s if s == self::selector("transferFrom(address,address,uint256)") =>
+				return self.transfer_from(gasometer, reader, context, is_static),
+			_ => return Err(gasometer.revert("unknown selector")),
+		};
+
+		Ok(gasometer.succeed(output.build(), vec![]))
+	}
+
+	fn transfer(
+		self,
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+		reader.expect_arguments(2)?;
+		let to = reader.read_address()?;
+		let amount = reader.read_u128()?;
+
+		gasometer.record_weight(self.token.transfer_weight())?;
+		self.token
+			.transfer(
+				&R::AddressMapping::into_account_id(context.caller),
+				&R::AddressMapping::into_account_id(to),
+				amount,
+			)
+			.map_err(|e| gasometer.revert(<&'static str>::from(e)))?;
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![Self::transfer_log(context.address, context.caller, to, amount)],
+		)?;
+		Ok(gasometer.succeed(EvmDataWriter::new().write_bool(true).build(), logs))
+	}
+
+	fn approve(
+		self,
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+		reader.expect_arguments(2)?;
+		let spender = reader.read_address()?;
+		let amount = reader.read_u256()?;
+
+		gasometer.record_writes(1)?;
+		pallet_erc20_allowances::Pallet::<R>::approve(
+			self.token.token(),
+			R::AddressMapping::into_account_id(context.caller),
+			R::AddressMapping::into_account_id(spender),
+			amount.min(U256::from(u128::MAX)).low_u128(),
+		);
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![log(
+				context.address,
+				vec![
+					event_topic("Approval(address,address,uint256)"),
+					address_topic(context.caller),
+					address_topic(spender),
+				],
+				EvmDataWriter::new().write_u256(amount).build(),
+			)],
+		)?;
+		Ok(gasometer.succeed(EvmDataWriter::new().write_bool(true).build(), logs))
+	}
+
+	fn transfer_from(
+		self,
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+		reader.expect_arguments(3)?;
+		let from = reader.read_address()?;
+		let to = reader.read_address()?;
+		let amount = reader.read_u128()?;
+
+		let owner = R::AddressMapping::into_account_id(from);
+		let spender = R::AddressMapping::into_account_id(context.caller);
+		let token = self.token.token();
+
+		// The allowance is checked before the transfer and spent after it, so that a failed
+		// transfer leaves it untouched.
+		gasometer.record_reads(1)?;
+		if pallet_erc20_allowances::Pallet::<R>::allowance(token, &owner, &spender) < amount {
+			return Err(gasometer.revert("insufficient allowance"))
+		}
+
+		gasometer.record_weight(self.token.transfer_weight())?;
+		self.token
+			.transfer(&owner, &R::AddressMapping::into_account_id(to), amount)
+			.map_err(|e| gasometer.revert(<&'static str>::from(e)))?;
+
+		gasometer.record_writes(1)?;
+		pallet_erc20_allowances::Pallet::<R>::spend_allowance(token, &owner, &spender, amount)
+			.map_err(|e| gasometer.revert(<&'static str>::from(e)))?;
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![Self::transfer_log(context.address, from, to, amount)],
+		)?;
+		Ok(gasometer.succeed(EvmDataWriter::new().write_bool(true).build(), logs))
+	}
+
+	fn transfer_log(address: H160, from: H160, to: H160, amount: u128) -> pallet_evm::Log {
+		log(
+			address,
+			vec![
+				event_topic("Transfer(address,address,uint256)"),
+				address_topic(from),
+				address_topic(to),
+			],
+			EvmDataWriter::new().write_u256(amount).build(),
+		)
+	}
+}
+
+/// The ERC-20 precompile of the native currency, named by `M`.
+pub struct NativeErc20Precompile<R, M>(PhantomData<(R, M)>);
+
+impl<R, M> Precompile for NativeErc20Precompile<R, M>
+where
+	R: pallet_evm::Config
+		+ pallet_balances::Config<Balance = u128>
+		+ pallet_erc20_allowances::Config<AssetId = AssetId, Balance = u128>,
+	M: NativeMetadata,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		Erc20::<R, _> { token: Native::<M>(PhantomData), _marker: PhantomData }
+			.execute(input, target_gas, context, is_static)
+	}
+}
+
+/// The ERC-20 precompiles of the assets, each at [`asset_address`] of its id.
+pub struct Erc20AssetsPrecompileSet<R>(PhantomData<R>);
+
+impl<R> Erc20AssetsPrecompileSet<R>
+where
+	R: pallet_evm::Config + pallet_assets::Config<AssetId = AssetId, Balance = u128>,
+{
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The asset whose ERC-20 is at `address`, if the asset exists.
+	fn asset(address: H160) -> Option<AssetId> {
+		// Assets can't be created with a zero minimum balance, which is returned for the
+		// assets that don't exist.
+		address_asset(address)
+			.filter(|id| !pallet_assets::Pallet::<R>::minimum_balance(*id).is_zero())
+	}
+}
+
+impl<R> PrecompileSet for Erc20AssetsPrecompileSet<R>
+where
+	R: pallet_evm::Config
+		+ pallet_assets::Config<AssetId = AssetId, Balance = u128>
+		+ pallet_erc20_allowances::Config<AssetId = AssetId, Balance = u128>,
+{
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		Self::asset(address).map(|id| {
+			Erc20::<R, _> { token: Asset(id), _marker: PhantomData }
+				.execute(input, target_gas, context, is_static)
+		})
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::asset(address).is_some()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{precompiles::SelMetadata, AccountId, Assets, Origin, Runtime, System, UNITS};
+	use frame_support::assert_ok;
+	use pallet_evm::PrecompileFailure;
+
+	type NativePrecompile = NativeErc20Precompile<Runtime, SelMetadata>;
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+	const BOB: H160 = H160::repeat_byte(0xbb);
+	const CHARLIE: H160 = H160::repeat_byte(0xcc);
+
+	fn account(address: H160) -> AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn context(address: H160, caller: H160) -> Context {
+		Context { address, caller, apparent_value: U256::zero() }
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(account(ALICE), 100 * UNITS), (account(BOB), 100 * UNITS)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Assets::force_create(Origin::root(), 7, account(ALICE).into(), true, 1));
+			assert_ok!(Assets::mint(Origin::signed(account(ALICE)), 7, account(ALICE).into(), 100));
+		});
+		ext
+	}
+
+	fn call(signature: &str, arguments: EvmDataWriter) -> Vec<u8> {
+		let mut input = selector(signature).to_vec();
+		input.extend(arguments.build());
+		input
+	}
+
+	fn native(input: &[u8], caller: H160) -> PrecompileResult {
+		NativePrecompile::execute(input, None, &context(H160::from_low_u64_be(2049), caller), false)
+	}
+
+	fn asset(id: AssetId, input: &[u8], caller: H160) -> Option<PrecompileResult> {
+		Erc20AssetsPrecompileSet::<Runtime>::new().execute(
+			asset_address(id),
+			input,
+			None,
+			&context(asset_address(id), caller),
+			false,
+		)
+	}
+
+	fn balance_of(who: H160) -> Vec<u8> {
+		call("balanceOf(address)", EvmDataWriter::new().write_address(who))
+	}
+
+	fn transfer(to: H160, amount: u128) -> Vec<u8> {
+		call("transfer(address,uint256)", EvmDataWriter::new().write_address(to).write_u256(amount))
+	}
+
+	#[test]
+	fn asset_addresses_round_trip() {
+		assert_eq!(address_asset(asset_address(7)), Some(7));
+		assert_eq!(address_asset(asset_address(u32::MAX)), Some(u32::MAX));
+		assert_eq!(address_asset(H160::from_low_u64_be(2049)), None);
+	}
+
+	#[test]
+	fn native_transfer_works() {
+		new_test_ext().execute_with(|| {
+			let output = native(&transfer(CHARLIE, UNITS), ALICE).unwrap();
+
+			assert_eq!(output.output, EvmDataWriter::new().write_bool(true).build());
+			assert_eq!(output.logs.len(), 1);
+			assert_eq!(output.logs[0].topics[0], event_topic("Transfer(address,address,uint256)"));
+			assert_eq!(output.logs[0].topics[1], address_topic(ALICE));
+			assert_eq!(output.logs[0].topics[2], address_topic(CHARLIE));
+			assert_eq!(
+				native(&balance_of(CHARLIE), BOB).unwrap().output,
+				EvmDataWriter::new().write_u256(UNITS).build()
+			);
+		});
+	}
+
+	#[test]
+	fn native_transfer_from_spends_allowance() {
+		new_test_ext().execute_with(|| {
+			let approve = call(
+				"approve(address,uint256)",
+				EvmDataWriter::new().write_address(BOB).write_u256(3 * UNITS),
+			);
+			let approval = native(&approve, ALICE).unwrap();
+			assert_eq!(
+				approval.logs[0].topics[0],
+				event_topic("Approval(address,address,uint256)")
+			);
+
+			let transfer_from = call(
+				"transferFrom(address,address,uint256)",
+				EvmDataWriter::new()
+					.write_address(ALICE)
+					.write_address(CHARLIE)
+					.write_u256(UNITS),
+			);
+			assert_ok!(native(&transfer_from, BOB));
+			assert_eq!(
+				pallet_erc20_allowances::Pallet::<Runtime>::allowance(
+					Token::Native,
+					&account(ALICE),
+					&account(BOB)
+				),
+				2 * UNITS
+			);
+
+			let allowance = call(
+				"allowance(address,address)",
+				EvmDataWriter::new().write_address(ALICE).write_address(BOB),
+			);
+			assert_eq!(
+				native(&allowance, CHARLIE).unwrap().output,
+				EvmDataWriter::new().write_u256(2 * UNITS).build()
+			);
+		});
+	}
+
+	#[test]
+	fn transfer_from_beyond_allowance_reverts() {
+		new_test_ext().execute_with(|| {
+			let transfer_from = call(
+				"transferFrom(address,address,uint256)",
+				EvmDataWriter::new()
+					.write_address(ALICE)
+					.write_address(CHARLIE)
+					.write_u256(UNITS),
+			);
+
+			assert!(matches!(native(&transfer_from, BOB), Err(PrecompileFailure::Revert { .. })));
+			assert_eq!(
+				native(&balance_of(CHARLIE), BOB).unwrap().output,
+				EvmDataWriter::new().write_u256(0u8).build()
+			);
+		});
+	}
+
+	#[test]
+	fn asset_precompiles_exist_for_created_assets() {
+		new_test_ext().execute_with(|| {
+			let precompiles = Erc20AssetsPrecompileSet::<Runtime>::new();
+
+			assert!(precompiles.is_precompile(asset_address(7)));
+			assert!(!precompiles.is_precompile(asset_address(8)));
+			assert!(asset(8, &balance_of(ALICE), ALICE).is_none());
+		});
+	}
+
+	#[test]
+	fn asset_transfer_works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(asset(7, &transfer(BOB, 40), ALICE).unwrap());
+
+			assert_eq!(Assets::balance(7, account(ALICE)), 60);
+			assert_eq!(
+				asset(7, &balance_of(BOB), ALICE).unwrap().unwrap().output,
+				EvmDataWriter::new().write_u256(40u8).build()
+			);
+			assert_eq!(
+				asset(7, &call("totalSupply()", EvmDataWriter::new()), ALICE)
+					.unwrap()
+					.unwrap()
+					.output,
+				EvmDataWriter::new().write_u256(100u8).build()
+			);
+		});
+	}
+}
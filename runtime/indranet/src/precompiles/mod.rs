@@ -0,0 +1,284 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The precompiled contracts of Indranet's EVM.
+
+pub mod dispatch;
+pub mod erc20;
+pub mod subscription;
+pub mod utils;
+pub mod xcm_transactor;
+
+use codec::Decode;
+use dispatch::DispatchPrecompile;
+use erc20::{Erc20AssetsPrecompileSet, NativeErc20Precompile, NativeMetadata};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::Contains,
+};
+use pallet_evm::{Context, Precompile, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_blake2::Blake2F;
+use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use parachains_common::AssetId;
+use sp_core::{H160, U256};
+use sp_std::{marker::PhantomData, vec::Vec};
+use subscription::SubscriptionPrecompile;
+use utils::revert;
+use xcm_transactor::XcmTransactorPrecompile;
+
+/// The name, symbol and decimals of SEL in its ERC-20 precompile.
+pub struct SelMetadata;
+
+impl NativeMetadata for SelMetadata {
+	fn name() -> &'static str {
+		"Selendra"
+	}
+
+	fn symbol() -> &'static str {
+		"SEL"
+	}
+
+	fn decimals() -> u8 {
+		18
+	}
+}
+
+/// The Ethereum precompiles at their usual addresses, followed by the Frontier ones from `1024`
+/// and the Indranet ones from `2048`. The assets' ERC-20 precompiles are at
+/// [`erc20::asset_address`] of their id. The dispatch precompile only dispatches the calls
+/// contained in `F`.
+#[derive(Default)]
+pub struct IndranetPrecompiles<R, F>(PhantomData<(R, F)>);
+
+impl<R, F> IndranetPrecompiles<R, F>
+where
+	R: pallet_evm::Config,
+{
+	pub fn new() -> Self {
+		Self(Default::default())
+	}
+
+	/// The addresses at which a precompile is deployed.
+	pub fn used_addresses() -> Vec<H160> {
+		sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 2048, 2049, 2050, 2051]
+			.into_iter()
+			.map(hash)
+			.collect()
+	}
+}
+
+impl<R, F> PrecompileSet for IndranetPrecompiles<R, F>
+where
+	R: pallet_evm::Config
+		+ pallet_subscription::Config
+		+ pallet_balances::Config<Balance = u128>
+		+ pallet_assets::Config<AssetId = AssetId, Balance = u128>
+		+ pallet_erc20_allowances::Config<AssetId = AssetId, Balance = u128>
+		+ pallet_xcm::Config,
+	R::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_subscription::Call<R>>
+		+ From<pallet_xcm::Call<R>>
+		+ Decode,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	subscription::BalanceOf<R>: From<u128>,
+	R::BlockNumber: Into<U256>,
+	F: Contains<R::Call>,
+{
+	fn execute(
+		&self,
+		address: H160,
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> Option<PrecompileResult> {
+		match address {
+			// Ethereum precompiles:
+			a if a == hash(1) => Some(ECRecover::execute(input, target_gas, context, is_static)),
+			a if a == hash(2) => Some(Sha256::execute(input, target_gas, context, is_static)),
+			a if a == hash(3) => Some(Ripemd160::execute(input, target_gas, context, is_static)),
+			a if a == hash(4) => Some(Identity::execute(input, target_gas, context, is_static)),
+			a if a == hash(5) => Some(Modexp::execute(input, target_gas, context, is_static)),
+			a if a == hash(6) => Some(Bn128Add::execute(input, target_gas, context, is_static)),
+			a if a == hash(7) => Some(Bn128Mul::execute(input, target_gas, context, is_static)),
+			a if a == hash(8) => Some(Bn128Pairing::execute(input, target_gas, context, is_static)),
+			a if a == hash(9) => Some(Blake2F::execute(input, target_gas, context, is_static)),
+			// Frontier precompiles:
+			a if a == hash(1024) =>
+				Some(Sha3FIPS256::execute(input, target_gas, context, is_static)),
+			a if a == hash(1025) =>
+				Some(ECRecoverPublicKey::execute(input, target_gas, context, is_static)),
+			// The Indranet precompiles act for their caller, which a contract running them in its
+			// own frame through DELEGATECALL or CALLCODE would pass off as the caller of its own.
+			a if a != context.address && self.is_precompile(a) =>
+				Some(Err(revert("cannot be called through DELEGATECALL or CALLCODE", 0))),
+			// Indranet precompiles:
+			a if a == hash(2048) =>
+				Some(SubscriptionPrecompile::<R>::execute(input, target_gas, context, is_static)),
+			a if a == hash(2049) => Some(NativeErc20Precompile::<R, SelMetadata>::execute(
+				input, target_gas, context, is_static,
+			)),
+			a if a == hash(2050) =>
+				Some(DispatchPrecompile::<R, F>::execute(input, target_gas, context, is_static)),
+			a if a == hash(2051) =>
+				Some(XcmTransactorPrecompile::<R>::execute(input, target_gas, context, is_static)),
+			// Assets' ERC-20 precompiles:
+			a => Erc20AssetsPrecompileSet::<R>::new()
+				.execute(a, input, target_gas, context, is_static),
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address) ||
+			Erc20AssetsPrecompileSet::<R>::new().is_precompile(address)
+	}
+}
+
+fn hash(a: u64) -> H160 {
+	H160::from_low_u64_be(a)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::utils::EvmDataWriter, AccountId, Assets, EvmDispatchCalls, Origin, Runtime,
+		UNITS,
+	};
+	use frame_support::assert_ok;
+	use pallet_evm::{AddressMapping, PrecompileFailure};
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+	/// A contract ALICE interacts with, running a precompile in its own frame.
+	const CONTRACT: H160 = H160::repeat_byte(0xcc);
+
+	fn account(address: H160) -> AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(account(ALICE), 100 * UNITS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		t.into()
+	}
+
+	/// Run the precompile at `address` as DELEGATECALL and CALLCODE run it, in the frame of
+	/// `CONTRACT` called by ALICE.
+	fn delegate_call(address: H160, input: &[u8]) -> Option<PrecompileResult> {
+		let context = Context { address: CONTRACT, caller: ALICE, apparent_value: U256::zero() };
+		IndranetPrecompiles::<Runtime, EvmDispatchCalls>::new()
+			.execute(address, input, None, &context, false)
+	}
+
+	fn assert_delegate_call_rejected(address: H160, input: &[u8]) {
+		let expected = revert("cannot be called through DELEGATECALL or CALLCODE", 0);
+		match (delegate_call(address, input), expected) {
+			(
+				Some(Err(PrecompileFailure::Revert { output, .. })),
+				PrecompileFailure::Revert { output: expected, .. },
+			) => assert_eq!(output, expected),
+			_ => panic!("expected a revert"),
+		}
+	}
+
+	#[test]
+	fn subscription_rejects_delegate_call() {
+		new_test_ext().execute_with(|| {
+			let call = |signature: &str, arguments: EvmDataWriter| {
+				let mut input = utils::selector(signature).to_vec();
+				input.extend(arguments.build());
+				input
+			};
+			let publish = call(
+				"publishService(uint256,string,string,uint32)",
+				EvmDataWriter::new()
+					.write_u256(UNITS)
+					.write_bytes(b"news")
+					.write_bytes(b"")
+					.write_u256(0u8),
+			);
+			let subscribe = call("subscribe(uint32)", EvmDataWriter::new().write_u256(0u8));
+			let renew = call("renew(uint32)", EvmDataWriter::new().write_u256(0u8));
+
+			for input in [publish, subscribe, renew] {
+				assert_delegate_call_rejected(hash(2048), &input);
+			}
+			assert_eq!(pallet_subscription::Pallet::<Runtime>::service_count(), 0);
+		});
+	}
+
+	#[test]
+	fn erc20_rejects_delegate_call() {
+		new_test_ext().execute_with(|| {
+			let mut transfer = utils::selector("transfer(address,uint256)").to_vec();
+			transfer.extend(EvmDataWriter::new().write_address(CONTRACT).write_u256(UNITS).build());
+
+			assert_delegate_call_rejected(hash(2049), &transfer);
+			assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(account(CONTRACT)), 0);
+
+			assert_ok!(Assets::force_create(Origin::root(), 7, account(ALICE).into(), true, 1));
+			assert_ok!(Assets::mint(Origin::signed(account(ALICE)), 7, account(ALICE).into(), 100));
+			assert_delegate_call_rejected(erc20::asset_address(7), &transfer);
+			assert_eq!(Assets::balance(7, account(CONTRACT)), 0);
+		});
+	}
+
+	#[test]
+	fn dispatch_rejects_delegate_call() {
+		new_test_ext().execute_with(|| {
+			let transfer = crate::Call::Balances(pallet_balances::Call::transfer {
+				dest: account(CONTRACT).into(),
+				value: UNITS,
+			});
+
+			assert_delegate_call_rejected(hash(2050), &codec::Encode::encode(&transfer));
+			assert_eq!(pallet_balances::Pallet::<Runtime>::free_balance(account(CONTRACT)), 0);
+		});
+	}
+
+	#[test]
+	fn xcm_transactor_rejects_delegate_call() {
+		new_test_ext().execute_with(|| {
+			let mut teleport = utils::selector("teleport(uint32,bytes32,uint256)").to_vec();
+			teleport.extend(
+				EvmDataWriter::new()
+					.write_u256(0u8)
+					.write_u256(0xccu8)
+					.write_u256(UNITS)
+					.build(),
+			);
+
+			assert_delegate_call_rejected(hash(2051), &teleport);
+			assert_eq!(
+				pallet_balances::Pallet::<Runtime>::free_balance(account(ALICE)),
+				100 * UNITS
+			);
+		});
+	}
+
+	#[test]
+	fn pure_precompiles_allow_delegate_call() {
+		new_test_ext().execute_with(|| {
+			assert!(matches!(delegate_call(hash(4), b"data"), Some(Ok(_))));
+			assert!(delegate_call(hash(3000), b"data").is_none());
+		});
+	}
+}
@@ -0,0 +1,434 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile giving EVM contracts access to `pallet_subscription`, with the interface in
+//! `Subscription.sol`.
+//!
+//! Calls are dispatched as the account the caller's address maps to, and charge the weight of
+//! the pallet's extrinsics. Each successful call emits a log mirroring the pallet's event.
+
+use super::utils::{
+	address_topic, event_topic, log, record_logs, selector, u256_topic, EvmDataReader,
+	EvmDataWriter, Gasometer,
+};
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::Currency,
+};
+use pallet_evm::{AddressMapping, Context, Precompile, PrecompileFailure, PrecompileResult};
+use pallet_subscription::ServiceIndex;
+use sp_core::{H160, U256};
+use sp_std::{marker::PhantomData, vec};
+
+pub type BalanceOf<R> = <<R as pallet_subscription::Config>::Currency as Currency<
+	<R as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// The `pallet_subscription` precompile.
+pub struct SubscriptionPrecompile<R>(PhantomData<R>);
+
+impl<R> Precompile for SubscriptionPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_subscription::Config,
+	R::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_subscription::Call<R>>,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	BalanceOf<R>: From<u128>,
+	R::BlockNumber: Into<U256>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::<R>::new(target_gas);
+		let (selector, reader) = EvmDataReader::new_with_selector(input)?;
+
+		if selector == self::selector("isSubscribed(uint32,address)") {
+			return Self::is_subscribed(gasometer, reader)
+		}
+
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+
+		match selector {
+			s if s == self::selector("publishService(uint256,string,string,uint32)") =>
+				Self::publish_service(gasometer, reader, context),
+			s if s == self::selector("subscribe(uint32)") =>
+				Self::subscribe(gasometer, reader, context),
+			s if s == self::selector("unsubscribe(uint32)") =>
+				Self::unsubscribe(gasometer, reader, context),
+			s if s == self::selector("renew(uint32)") => Self::renew(gasometer, reader, context),
+			_ => Err(gasometer.revert("unknown selector")),
+		}
+	}
+}
+
+impl<R> SubscriptionPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_subscription::Config,
+	R::Call: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_subscription::Call<R>>,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	BalanceOf<R>: From<u128>,
+	R::BlockNumber: Into<U256>,
+{
+	fn publish_service(
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+	) -> PrecompileResult {
+		reader.expect_arguments(4)?;
+		let cost = reader.read_u128()?;
+		let name = reader.read_bytes()?;
+		let description = reader.read_bytes()?;
+		let period = reader.read_u32()?;
+
+		let service_id = pallet_subscription::Pallet::<R>::service_count();
+		let publisher = R::AddressMapping::into_account_id(context.caller);
+		Self::dispatch(
+			&mut gasometer,
+			publisher,
+			pallet_subscription::Call::<R>::publish_service {
+				cost: cost.into(),
+				name,
+				description,
+				maybe_periodic: if period == 0 { None } else { Some(period) },
+			},
+		)?;
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![log(
+				context.address,
+				vec![
+					event_topic("ServicePublished(uint32,address)"),
+					u256_topic(service_id),
+					address_topic(context.caller),
+				],
+				vec![],
+			)],
+		)?;
+		Ok(gasometer.succeed(EvmDataWriter::new().write_u256(service_id).build(), logs))
+	}
+
+	fn subscribe(
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+	) -> PrecompileResult {
+		reader.expect_arguments(1)?;
+		let service_id = reader.read_u32()?;
+
+		let subscriber = R::AddressMapping::into_account_id(context.caller);
+		Self::dispatch(
+			&mut gasometer,
+			subscriber,
+			pallet_subscription::Call::<R>::subscribe_service { service_id },
+		)?;
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![Self::subscriber_log("ServiceSubscribed(uint32,address)", service_id, context)],
+		)?;
+		Ok(gasometer.succeed(vec![], logs))
+	}
+
+	fn unsubscribe(
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+	) -> PrecompileResult {
+		reader.expect_arguments(1)?;
+		let service_id = reader.read_u32()?;
+
+		let subscriber = R::AddressMapping::into_account_id(context.caller);
+		Self::dispatch(
+			&mut gasometer,
+			subscriber,
+			pallet_subscription::Call::<R>::unsubscribe_service { service_id },
+		)?;
+
+		let logs = record_logs(
+			&mut gasometer,
+			vec![Self::subscriber_log("ServiceUnsubscribed(uint32,address)", service_id, context)],
+		)?;
+		Ok(gasometer.succeed(vec![], logs))
+	}
+
+	fn renew(
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+	) -> PrecompileResult {
+		reader.expect_arguments(1)?;
+		let service_id = reader.read_u32()?;
+
+		let subscriber = R::AddressMapping::into_account_id(context.caller);
+		Self::dispatch(
+			&mut gasometer,
+			subscriber.clone(),
+			pallet_subscription::Call::<R>::renew_subscription { service_id },
+		)?;
+
+		gasometer.record_reads(1)?;
+		let expire_on: U256 =
+			pallet_subscription::Pallet::<R>::subscriptions(service_id, subscriber)
+				.and_then(|subscription| subscription.expire_on())
+				.map(Into::into)
+				.unwrap_or_default();
+
+		let mut renewed = Self::subscriber_log(
+			"SubscriptionRenewed(uint32,address,uint256)",
+			service_id,
+			context,
+		);
+		renewed.data = EvmDataWriter::new().write_u256(expire_on).build();
+		let logs = record_logs(&mut gasometer, vec![renewed])?;
+		Ok(gasometer.succeed(EvmDataWriter::new().write_u256(expire_on).build(), logs))
+	}
+
+	fn is_subscribed(mut gasometer: Gasometer<R>, mut reader: EvmDataReader) -> PrecompileResult {
+		reader.expect_arguments(2)?;
+		let service_id = reader.read_u32()?;
+		let subscriber = R::AddressMapping::into_account_id(reader.read_address()?);
+
+		gasometer.record_reads(1)?;
+		let subscribed = pallet_subscription::Pallet::<R>::is_subscribed(service_id, &subscriber);
+
+		Ok(gasometer.succeed(EvmDataWriter::new().write_bool(subscribed).build(), vec![]))
+	}
+
+	/// Dispatch `call` as `who`.
+	fn dispatch(
+		gasometer: &mut Gasometer<R>,
+		who: R::AccountId,
+		call: pallet_subscription::Call<R>,
+	) -> Result<(), PrecompileFailure> {
+		gasometer.dispatch(who, call).map(|_| ())
+	}
+
+	/// A log of `signature` indexed by the service and the caller.
+	fn subscriber_log(
+		signature: &str,
+		service_id: ServiceIndex,
+		context: &Context,
+	) -> pallet_evm::Log {
+		log(
+			context.address,
+			vec![event_topic(signature), u256_topic(service_id), address_topic(context.caller)],
+			vec![],
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Balances, Origin, Runtime, Subscription, System, UNITS};
+	use frame_support::assert_ok;
+	use pallet_evm::{ExitSucceed, PrecompileOutput};
+	use pallet_studentid::Judgement;
+
+	type Precompile = SubscriptionPrecompile<Runtime>;
+
+	const PUBLISHER: H160 = H160::repeat_byte(0xaa);
+	const SUBSCRIBER: H160 = H160::repeat_byte(0xbb);
+
+	fn account(address: H160) -> crate::AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn context(caller: H160) -> Context {
+		Context { address: H160::from_low_u64_be(2048), caller, apparent_value: U256::zero() }
+	}
+
+	/// Link `who` to an identity judged accurate, as `EnsureVerifiedIdentity` expects of
+	/// publishers.
+	fn verify_identity(who: H160) {
+		let email: pallet_studentid::Email<Runtime> =
+			b"publisher@indra.net".to_vec().try_into().unwrap();
+		pallet_studentid::EmailId::<Runtime>::insert(account(who), email.clone());
+		pallet_studentid::Judgements::<Runtime>::insert(
+			email,
+			pallet_studentid::JudgementsOf::<Runtime>::try_from(vec![(0, Judgement::KnownGood)])
+				.unwrap(),
+		);
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(account(PUBLISHER), 100 * UNITS), (account(SUBSCRIBER), 100 * UNITS)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Subscription::request_approved_publisher(Origin::signed(account(
+				PUBLISHER
+			))));
+			assert_ok!(Subscription::approve_publisher(Origin::root(), account(PUBLISHER)));
+			verify_identity(PUBLISHER);
+		});
+		ext
+	}
+
+	fn call(signature: &str, arguments: EvmDataWriter) -> Vec<u8> {
+		let mut input = selector(signature).to_vec();
+		input.extend(arguments.build());
+		input
+	}
+
+	fn publish_service_input(cost: u128, period: u32) -> Vec<u8> {
+		let mut input = call(
+			"publishService(uint256,string,string,uint32)",
+			EvmDataWriter::new()
+				.write_u256(cost)
+				.write_u256(128u8)
+				.write_u256(192u8)
+				.write_u256(period),
+		);
+		input.extend(EvmDataWriter::new().write_u256(4u8).build());
+		input.extend(*b"news");
+		input.resize(input.len() + 28, 0);
+		input.extend(EvmDataWriter::new().write_u256(5u8).build());
+		input.extend(*b"daily");
+		input.resize(input.len() + 27, 0);
+		input
+	}
+
+	fn execute(input: &[u8], caller: H160) -> PrecompileResult {
+		Precompile::execute(input, None, &context(caller), false)
+	}
+
+	fn is_subscribed(service_id: u32, who: H160) -> bool {
+		let input = call(
+			"isSubscribed(uint32,address)",
+			EvmDataWriter::new().write_u256(service_id).write_address(who),
+		);
+		let output = Precompile::execute(&input, None, &context(who), true).unwrap();
+		output.output == EvmDataWriter::new().write_bool(true).build()
+	}
+
+	#[test]
+	fn publish_service_returns_service_id_and_logs() {
+		new_test_ext().execute_with(|| {
+			let PrecompileOutput { exit_status, output, logs, cost } =
+				execute(&publish_service_input(UNITS, 5), PUBLISHER).unwrap();
+
+			assert_eq!(exit_status, ExitSucceed::Returned);
+			assert_eq!(output, EvmDataWriter::new().write_u256(0u8).build());
+			assert!(cost > 0);
+			assert_eq!(logs.len(), 1);
+			assert_eq!(logs[0].topics[0], event_topic("ServicePublished(uint32,address)"));
+			assert_eq!(logs[0].topics[2], address_topic(PUBLISHER));
+
+			assert!(Subscription::services(0).is_some());
+			assert_eq!(Subscription::service_count(), 1);
+			assert_eq!(Subscription::service_descriptions(0).unwrap().to_vec(), b"daily".to_vec());
+		});
+	}
+
+	#[test]
+	fn publish_service_requires_verified_identity() {
+		new_test_ext().execute_with(|| {
+			pallet_studentid::EmailId::<Runtime>::remove(account(PUBLISHER));
+
+			assert!(matches!(
+				execute(&publish_service_input(UNITS, 5), PUBLISHER),
+				Err(PrecompileFailure::Revert { .. })
+			));
+			assert_eq!(Subscription::service_count(), 0);
+		});
+	}
+
+	#[test]
+	fn subscribe_renew_and_unsubscribe_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(execute(&publish_service_input(UNITS, 5), PUBLISHER));
+			let subscribe = call("subscribe(uint32)", EvmDataWriter::new().write_u256(0u8));
+
+			assert_ok!(execute(&subscribe, SUBSCRIBER));
+			assert!(is_subscribed(0, SUBSCRIBER));
+			assert_eq!(Balances::free_balance(account(SUBSCRIBER)), 99 * UNITS);
+
+			System::set_block_number(6);
+			assert!(!is_subscribed(0, SUBSCRIBER));
+
+			let renewed =
+				execute(&call("renew(uint32)", EvmDataWriter::new().write_u256(0u8)), SUBSCRIBER)
+					.unwrap();
+			assert_eq!(renewed.output, EvmDataWriter::new().write_u256(11u8).build());
+			assert_eq!(renewed.logs[0].data, renewed.output);
+			assert!(is_subscribed(0, SUBSCRIBER));
+
+			assert_ok!(execute(
+				&call("unsubscribe(uint32)", EvmDataWriter::new().write_u256(0u8)),
+				SUBSCRIBER
+			));
+			assert!(!is_subscribed(0, SUBSCRIBER));
+		});
+	}
+
+	#[test]
+	fn failed_dispatch_reverts() {
+		new_test_ext().execute_with(|| {
+			let subscribe = call("subscribe(uint32)", EvmDataWriter::new().write_u256(0u8));
+
+			assert!(matches!(
+				execute(&subscribe, SUBSCRIBER),
+				Err(PrecompileFailure::Revert { .. })
+			));
+			assert!(!is_subscribed(0, SUBSCRIBER));
+		});
+	}
+
+	#[test]
+	fn state_changes_are_rejected_in_static_calls() {
+		new_test_ext().execute_with(|| {
+			assert!(matches!(
+				Precompile::execute(
+					&publish_service_input(UNITS, 0),
+					None,
+					&context(PUBLISHER),
+					true
+				),
+				Err(PrecompileFailure::Revert { .. })
+			));
+			assert_eq!(Subscription::service_count(), 0);
+		});
+	}
+
+	#[test]
+	fn out_of_gas_fails() {
+		new_test_ext().execute_with(|| {
+			assert!(matches!(
+				Precompile::execute(
+					&publish_service_input(UNITS, 0),
+					Some(1),
+					&context(PUBLISHER),
+					false
+				),
+				Err(PrecompileFailure::Error { .. })
+			));
+			assert_eq!(Subscription::service_count(), 0);
+		});
+	}
+}
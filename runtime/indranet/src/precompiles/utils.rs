@@ -0,0 +1,405 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Solidity ABI encoding, gas accounting and dispatch helpers shared by the precompiles.
+
+use frame_support::{
+	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+	traits::Get,
+	weights::Weight,
+};
+use pallet_evm::{
+	Context, ExitError, ExitRevert, ExitSucceed, GasWeightMapping, Log, PrecompileFailure,
+	PrecompileOutput,
+};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Gas charged for a log, and for each of its topics and data bytes, as in the `LOG` opcodes.
+const LOG_GAS: u64 = 375;
+const LOG_TOPIC_GAS: u64 = 375;
+const LOG_DATA_GAS: u64 = 8;
+
+/// The 4 byte selector of a Solidity function, from its signature such as
+/// `transfer(address,uint256)`.
+pub fn selector(signature: &str) -> [u8; 4] {
+	let hash = keccak_256(signature.as_bytes());
+	[hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// The first topic of a Solidity event, from its signature such as
+/// `Transfer(address,address,uint256)`.
+pub fn event_topic(signature: &str) -> H256 {
+	H256(keccak_256(signature.as_bytes()))
+}
+
+/// An address as an indexed event topic.
+pub fn address_topic(address: H160) -> H256 {
+	address.into()
+}
+
+/// A number as an indexed event topic.
+pub fn u256_topic(value: impl Into<U256>) -> H256 {
+	let mut topic = H256::zero();
+	value.into().to_big_endian(topic.as_bytes_mut());
+	topic
+}
+
+/// A log emitted by the precompile at `address`.
+pub fn log(address: H160, topics: Vec<H256>, data: Vec<u8>) -> Log {
+	Log { address, topics, data }
+}
+
+/// Reads the Solidity ABI encoded arguments of a call.
+pub struct EvmDataReader<'a> {
+	input: &'a [u8],
+	cursor: usize,
+}
+
+impl<'a> EvmDataReader<'a> {
+	/// Split the function selector from the arguments of `input`.
+	pub fn new_with_selector(input: &'a [u8]) -> Result<([u8; 4], Self), PrecompileFailure> {
+		if input.len() < 4 {
+			return Err(revert("tried to parse selector out of bounds", 0))
+		}
+
+		let mut selector = [0u8; 4];
+		selector.copy_from_slice(&input[..4]);
+		Ok((selector, Self { input: &input[4..], cursor: 0 }))
+	}
+
+	/// Fail unless at least `count` arguments are left to read.
+	pub fn expect_arguments(&self, count: usize) -> Result<(), PrecompileFailure> {
+		if self.input.len() >= self.cursor + count * 32 {
+			Ok(())
+		} else {
+			Err(revert("input doesn't match expected length", 0))
+		}
+	}
+
+	fn read_word(&mut self) -> Result<&'a [u8], PrecompileFailure> {
+		let end = self.cursor.checked_add(32).ok_or_else(|| revert("word offset overflow", 0))?;
+		let word = self
+			.input
+			.get(self.cursor..end)
+			.ok_or_else(|| revert("tried to parse word out of bounds", 0))?;
+		self.cursor = end;
+		Ok(word)
+	}
+
+	/// Read a `uint256`.
+	pub fn read_u256(&mut self) -> Result<U256, PrecompileFailure> {
+		Ok(U256::from_big_endian(self.read_word()?))
+	}
+
+	/// Read an unsigned integer that must fit in a `u128`.
+	pub fn read_u128(&mut self) -> Result<u128, PrecompileFailure> {
+		let value = self.read_u256()?;
+		if value > U256::from(u128::MAX) {
+			return Err(revert("value is too large", 0))
+		}
+		Ok(value.low_u128())
+	}
+
+	/// Read an unsigned integer that must fit in a `u32`.
+	pub fn read_u32(&mut self) -> Result<u32, PrecompileFailure> {
+		let value = self.read_u256()?;
+		if value > U256::from(u32::MAX) {
+			return Err(revert("value is too large", 0))
+		}
+		Ok(value.low_u32())
+	}
+
+	/// Read an unsigned integer that must fit in a `u64`.
+	pub fn read_u64(&mut self) -> Result<u64, PrecompileFailure> {
+		let value = self.read_u256()?;
+		if value > U256::from(u64::MAX) {
+			return Err(revert("value is too large", 0))
+		}
+		Ok(value.low_u64())
+	}
+
+	/// Read a `bytes32`.
+	pub fn read_bytes32(&mut self) -> Result<[u8; 32], PrecompileFailure> {
+		let mut bytes = [0u8; 32];
+		bytes.copy_from_slice(self.read_word()?);
+		Ok(bytes)
+	}
+
+	/// Read a `bool`.
+	pub fn read_bool(&mut self) -> Result<bool, PrecompileFailure> {
+		let value = self.read_u256()?;
+		if value > U256::one() {
+			return Err(revert("invalid bool", 0))
+		}
+		Ok(!value.is_zero())
+	}
+
+	/// Read an `address`.
+	pub fn read_address(&mut self) -> Result<H160, PrecompileFailure> {
+		Ok(H160::from_slice(&self.read_word()?[12..]))
+	}
+
+	/// Read a dynamic `bytes` or `string`, through its offset from the start of the arguments.
+	pub fn read_bytes(&mut self) -> Result<Vec<u8>, PrecompileFailure> {
+		let offset = self.read_u32()? as usize;
+
+		let mut inner = Self { input: self.input, cursor: offset };
+		let len = inner.read_u32()? as usize;
+		let start = inner.cursor;
+		let end = start.checked_add(len).ok_or_else(|| revert("bytes length overflow", 0))?;
+
+		inner
+			.input
+			.get(start..end)
+			.map(|bytes| bytes.to_vec())
+			.ok_or_else(|| revert("tried to parse bytes out of bounds", 0))
+	}
+}
+
+/// Writes Solidity ABI encoded static values, such as return values or log data.
+#[derive(Default)]
+pub struct EvmDataWriter {
+	data: Vec<u8>,
+}
+
+impl EvmDataWriter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Write a `uint256`, or any smaller unsigned integer.
+	pub fn write_u256(mut self, value: impl Into<U256>) -> Self {
+		let mut word = [0u8; 32];
+		value.into().to_big_endian(&mut word);
+		self.data.extend_from_slice(&word);
+		self
+	}
+
+	/// Write a `bool`.
+	pub fn write_bool(self, value: bool) -> Self {
+		self.write_u256(value as u8)
+	}
+
+	/// Write an `address`.
+	pub fn write_address(mut self, value: H160) -> Self {
+		self.data.extend_from_slice(H256::from(value).as_bytes());
+		self
+	}
+
+	/// Write a dynamic `bytes` or `string`, which must be the last value written.
+	pub fn write_bytes(mut self, value: &[u8]) -> Self {
+		let offset = self.data.len() + 32;
+		self = self.write_u256(offset).write_u256(value.len());
+		self.data.extend_from_slice(value);
+		self.data.resize(self.data.len() + (32 - value.len() % 32) % 32, 0);
+		self
+	}
+
+	pub fn build(self) -> Vec<u8> {
+		self.data
+	}
+}
+
+/// A revert carrying `message` as a Solidity `Error(string)`, after `cost` gas was used.
+pub fn revert(message: impl AsRef<[u8]>, cost: u64) -> PrecompileFailure {
+	let mut output = selector("Error(string)").to_vec();
+	output.extend(EvmDataWriter::new().write_bytes(message.as_ref()).build());
+
+	PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output, cost }
+}
+
+/// Keeps track of the gas used by a precompile, within the gas it was given.
+pub struct Gasometer<R> {
+	used: u64,
+	target_gas: Option<u64>,
+	_marker: PhantomData<R>,
+}
+
+impl<R: pallet_evm::Config> Gasometer<R> {
+	pub fn new(target_gas: Option<u64>) -> Self {
+		Self { used: 0, target_gas, _marker: PhantomData }
+	}
+
+	/// The gas used so far.
+	pub fn used(&self) -> u64 {
+		self.used
+	}
+
+	/// Charge `cost` gas, failing if it exceeds the gas given to the precompile.
+	pub fn record_cost(&mut self, cost: u64) -> Result<(), PrecompileFailure> {
+		self.used = self.used.saturating_add(cost);
+		match self.target_gas {
+			Some(target_gas) if self.used > target_gas =>
+				Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }),
+			_ => Ok(()),
+		}
+	}
+
+	/// Charge the gas equivalent of `weight`.
+	pub fn record_weight(&mut self, weight: Weight) -> Result<(), PrecompileFailure> {
+		self.record_cost(R::GasWeightMapping::weight_to_gas(weight))
+	}
+
+	/// Charge the gas equivalent of `reads` storage reads.
+	pub fn record_reads(&mut self, reads: u64) -> Result<(), PrecompileFailure> {
+		self.record_weight(<R as frame_system::Config>::DbWeight::get().reads(reads))
+	}
+
+	/// Charge the gas equivalent of `writes` storage writes.
+	pub fn record_writes(&mut self, writes: u64) -> Result<(), PrecompileFailure> {
+		self.record_weight(<R as frame_system::Config>::DbWeight::get().writes(writes))
+	}
+
+	/// Charge the gas of the `LOG` opcode emitting `log`.
+	pub fn record_log(&mut self, log: &Log) -> Result<(), PrecompileFailure> {
+		self.record_cost(
+			LOG_GAS +
+				LOG_TOPIC_GAS * log.topics.len() as u64 +
+				LOG_DATA_GAS * log.data.len() as u64,
+		)
+	}
+
+	/// Give back the gas charged beyond `actual` out of an `estimated` weight.
+	pub fn refund_weight(&mut self, estimated: Weight, actual: Weight) {
+		let refund = R::GasWeightMapping::weight_to_gas(estimated.saturating_sub(actual));
+		self.used = self.used.saturating_sub(refund);
+	}
+
+	/// A revert carrying `message`, charging the gas used so far.
+	pub fn revert(&self, message: impl AsRef<[u8]>) -> PrecompileFailure {
+		revert(message, self.used)
+	}
+
+	/// Fail with a revert when called from a `STATICCALL`, for functions that modify state.
+	pub fn check_not_static(&self, is_static: bool) -> Result<(), PrecompileFailure> {
+		if is_static {
+			Err(self.revert("can't call non-static function in static context"))
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Fail with a revert when called with a value, for functions that are not payable.
+	pub fn check_no_value(&self, context: &Context) -> Result<(), PrecompileFailure> {
+		if context.apparent_value.is_zero() {
+			Ok(())
+		} else {
+			Err(self.revert("function is not payable"))
+		}
+	}
+
+	/// Dispatch `call` as `origin`, charging the weight it declares and refunding the part of it
+	/// left unused. A failed dispatch reverts with the dispatch error.
+	pub fn dispatch<Call>(
+		&mut self,
+		origin: R::AccountId,
+		call: Call,
+	) -> Result<PostDispatchInfo, PrecompileFailure>
+	where
+		R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + From<Call>,
+		<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+	{
+		let call = R::Call::from(call);
+		let weight = call.get_dispatch_info().weight;
+		self.record_weight(weight)?;
+
+		let post_info = call.dispatch(Some(origin).into()).map_err(|e| {
+			self.refund_weight(weight, e.post_info.actual_weight.unwrap_or(weight));
+			self.revert(<&'static str>::from(e.error))
+		})?;
+		self.refund_weight(weight, post_info.actual_weight.unwrap_or(weight));
+
+		Ok(post_info)
+	}
+
+	/// A successful output returning `output` and emitting `logs`, whose gas must have been
+	/// recorded already.
+	pub fn succeed(self, output: Vec<u8>, logs: Vec<Log>) -> PrecompileOutput {
+		PrecompileOutput { exit_status: ExitSucceed::Returned, cost: self.used, output, logs }
+	}
+}
+
+/// Charge the gas of `logs`, and return them.
+pub fn record_logs<R: pallet_evm::Config>(
+	gasometer: &mut Gasometer<R>,
+	logs: Vec<Log>,
+) -> Result<Vec<Log>, PrecompileFailure> {
+	for log in &logs {
+		gasometer.record_log(log)?;
+	}
+	Ok(logs)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn selectors_match_solidity() {
+		assert_eq!(selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+		assert_eq!(selector("balanceOf(address)"), [0x70, 0xa0, 0x82, 0x31]);
+		assert_eq!(
+			event_topic("Transfer(address,address,uint256)"),
+			H256(hex_literal::hex!(
+				"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+			))
+		);
+	}
+
+	#[test]
+	fn reader_decodes_static_and_dynamic_arguments() {
+		let mut input = selector("f(uint32,bytes,address)").to_vec();
+		input.extend(
+			EvmDataWriter::new()
+				.write_u256(7u8)
+				.write_u256(96u8)
+				.write_address(H160::repeat_byte(0x11))
+				.write_u256(3u8)
+				.build(),
+		);
+		input.extend_from_slice(&[1, 2, 3]);
+		input.resize(input.len() + 29, 0);
+
+		let (selector, mut reader) = EvmDataReader::new_with_selector(&input).unwrap();
+		assert_eq!(selector, super::selector("f(uint32,bytes,address)"));
+		assert_eq!(reader.read_u32().unwrap(), 7);
+		assert_eq!(reader.read_bytes().unwrap(), vec![1, 2, 3]);
+		assert_eq!(reader.read_address().unwrap(), H160::repeat_byte(0x11));
+		assert!(reader.read_u256().is_err());
+	}
+
+	#[test]
+	fn reader_rejects_out_of_bounds_offsets() {
+		let input = EvmDataWriter::new().write_u256(u32::MAX).build();
+
+		let mut reader = EvmDataReader { input: &input, cursor: 0 };
+		assert!(reader.read_bytes().is_err());
+	}
+
+	#[test]
+	fn revert_encodes_error_string() {
+		match revert("nope", 5) {
+			PrecompileFailure::Revert { output, cost, .. } => {
+				assert_eq!(cost, 5);
+				assert_eq!(&output[..4], &[0x08, 0xc3, 0x79, 0xa0]);
+				assert_eq!(output.len(), 4 + 32 * 3);
+				assert_eq!(&output[68..72], b"nope");
+			},
+			_ => panic!("expected a revert"),
+		}
+	}
+}
@@ -0,0 +1,184 @@
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A precompile moving SEL cross-chain through `pallet_xcm`, with the interface in
+//! `XcmTransactor.sol`.
+//!
+//! Transfers are dispatched as the account the caller's address maps to, so they are subject to
+//! the same checks as the `pallet_xcm` extrinsics. Arbitrary messages such as `Transact` are not
+//! sent, since the runtime's `SendXcmOrigin` lets no one send them.
+
+use super::utils::{selector, EvmDataReader, Gasometer};
+use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::{AddressMapping, Context, Precompile, PrecompileResult};
+use sp_std::{boxed::Box, marker::PhantomData, vec};
+use xcm::{latest::prelude::*, VersionedMultiAssets, VersionedMultiLocation};
+
+/// The XCM transactor precompile.
+pub struct XcmTransactorPrecompile<R>(PhantomData<R>);
+
+impl<R> Precompile for XcmTransactorPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_xcm::Config,
+	R::Call:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + From<pallet_xcm::Call<R>>,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		context: &Context,
+		is_static: bool,
+	) -> PrecompileResult {
+		let mut gasometer = Gasometer::<R>::new(target_gas);
+		let (selector, reader) = EvmDataReader::new_with_selector(input)?;
+
+		gasometer.check_not_static(is_static)?;
+		gasometer.check_no_value(context)?;
+
+		match selector {
+			s if s == self::selector("reserveTransfer(uint32,bytes32,uint256)") =>
+				Self::transfer(gasometer, reader, context, false),
+			s if s == self::selector("teleport(uint32,bytes32,uint256)") =>
+				Self::transfer(gasometer, reader, context, true),
+			_ => Err(gasometer.revert("unknown selector")),
+		}
+	}
+}
+
+impl<R> XcmTransactorPrecompile<R>
+where
+	R: pallet_evm::Config + pallet_xcm::Config,
+	R::Call:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + From<pallet_xcm::Call<R>>,
+	<R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+{
+	fn transfer(
+		mut gasometer: Gasometer<R>,
+		mut reader: EvmDataReader,
+		context: &Context,
+		teleport: bool,
+	) -> PrecompileResult {
+		reader.expect_arguments(3)?;
+		let dest = Box::new(VersionedMultiLocation::from(destination(reader.read_u32()?)));
+		let beneficiary = Box::new(VersionedMultiLocation::from(MultiLocation::from(
+			account_junction(reader.read_bytes32()?),
+		)));
+		let assets =
+			Box::new(VersionedMultiAssets::from(MultiAssets::from(sel(reader.read_u128()?))));
+
+		let call = if teleport {
+			pallet_xcm::Call::<R>::teleport_assets { dest, beneficiary, assets, fee_asset_item: 0 }
+		} else {
+			pallet_xcm::Call::<R>::reserve_transfer_assets {
+				dest,
+				beneficiary,
+				assets,
+				fee_asset_item: 0,
+			}
+		};
+		gasometer.dispatch(R::AddressMapping::into_account_id(context.caller), call)?;
+
+		Ok(gasometer.succeed(vec![], vec![]))
+	}
+}
+
+/// The relay chain for `para_id` `0`, and the sibling parachain `para_id` otherwise.
+fn destination(para_id: u32) -> MultiLocation {
+	match para_id {
+		0 => MultiLocation::parent(),
+		id => MultiLocation::new(1, X1(Parachain(id))),
+	}
+}
+
+/// The junction of the account `id` on any network.
+fn account_junction(id: [u8; 32]) -> Junction {
+	AccountId32 { network: NetworkId::Any, id }
+}
+
+/// `amount` of SEL, which is the relay chain's token as per `CurrencyTransactor`.
+fn sel(amount: u128) -> MultiAsset {
+	(MultiLocation::parent(), amount).into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		precompiles::utils::{revert, EvmDataWriter},
+		AccountId, Balances, Runtime, System, UNITS,
+	};
+	use pallet_evm::PrecompileFailure;
+	use sp_core::{H160, U256};
+
+	type Precompile = XcmTransactorPrecompile<Runtime>;
+
+	const ALICE: H160 = H160::repeat_byte(0xaa);
+
+	fn account(address: H160) -> AccountId {
+		<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(account(ALICE), 100 * UNITS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	fn execute(input: &[u8], is_static: bool) -> PrecompileResult {
+		let context = Context {
+			address: H160::from_low_u64_be(2051),
+			caller: ALICE,
+			apparent_value: U256::zero(),
+		};
+		Precompile::execute(input, None, &context, is_static)
+	}
+
+	fn revert_data(result: PrecompileResult) -> Vec<u8> {
+		match result {
+			Err(PrecompileFailure::Revert { output, .. }) => output,
+			_ => panic!("expected a revert"),
+		}
+	}
+
+	#[test]
+	fn static_calls_are_rejected() {
+		new_test_ext().execute_with(|| {
+			let mut input = selector("teleport(uint32,bytes32,uint256)").to_vec();
+			input.extend(
+				EvmDataWriter::new().write_u256(0u8).write_u256(0u8).write_u256(UNITS).build(),
+			);
+
+			assert!(matches!(execute(&input, true), Err(PrecompileFailure::Revert { .. })));
+			assert_eq!(Balances::free_balance(account(ALICE)), 100 * UNITS);
+		});
+	}
+
+	#[test]
+	fn transact_is_not_sent() {
+		new_test_ext().execute_with(|| {
+			let input = selector("transact(uint32,bytes,uint64,uint256)");
+
+			assert_eq!(
+				revert_data(execute(&input, false)),
+				revert_data(Err(revert("unknown selector", 0)))
+			);
+		});
+	}
+}
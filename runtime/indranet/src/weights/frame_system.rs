@@ -1,86 +0,0 @@
-// Copyright 2021 Parity Technologies (UK) Ltd.
-// This file is part of Cumulus.
-
-// Cumulus is free software: you can redistribute it and/or modify
-// it under the terms of the GNU General Public License as published by
-// the Free Software Foundation, either version 3 of the License, or
-// (at your option) any later version.
-
-// Cumulus is distributed in the hope that it will be useful,
-// but WITHOUT ANY WARRANTY; without even the implied warranty of
-// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-// GNU General Public License for more details.
-
-// You should have received a copy of the GNU General Public License
-// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
-
-//! Autogenerated weights for `frame_system`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-11-12, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("indranet-dev"), DB CACHE: 128
-
-// Executed Command:
-// ./target/release/selendra-collator
-// benchmark
-// --chain=indranet-dev
-// --execution=wasm
-// --wasm-execution=compiled
-// --pallet=frame_system
-// --extrinsic=*
-// --steps=50
-// --repeat=20
-// --raw
-// --header=./file_header.txt
-// --output=./selendra-parachains/indranet/src/weights
-
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::Weight};
-use sp_std::marker::PhantomData;
-
-/// Weight functions for `frame_system`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> frame_system::WeightInfo for WeightInfo<T> {
-	fn remark(b: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 0
-			.saturating_add((1_000 as Weight).saturating_mul(b as Weight))
-	}
-	fn remark_with_event(b: u32, ) -> Weight {
-		(606_000 as Weight)
-			// Standard Error: 0
-			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
-	}
-	// Storage: System Digest (r:1 w:1)
-	// Storage: unknown [0x3a686561707061676573] (r:0 w:1)
-	fn set_heap_pages() -> Weight {
-		(4_704_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Skipped Metadata (r:0 w:0)
-	fn set_storage(i: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 0
-			.saturating_add((770_000 as Weight).saturating_mul(i as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
-	}
-	// Storage: Skipped Metadata (r:0 w:0)
-	fn kill_storage(i: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 0
-			.saturating_add((510_000 as Weight).saturating_mul(i as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
-	}
-	// Storage: Skipped Metadata (r:0 w:0)
-	fn kill_prefix(p: u32, ) -> Weight {
-		(3_350_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((739_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
-	}
-}
@@ -1,10 +0,0 @@
-pub mod frame_system;
-pub mod pallet_assets;
-pub mod pallet_balances;
-pub mod pallet_collator_selection;
-pub mod pallet_multisig;
-pub mod pallet_proxy;
-pub mod pallet_session;
-pub mod pallet_timestamp;
-pub mod pallet_uniques;
-pub mod pallet_utility;
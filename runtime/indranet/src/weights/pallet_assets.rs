@@ -1,227 +0,0 @@
-// Copyright 2021 Parity Technologies (UK) Ltd.
-// This file is part of Cumulus.
-
-// Cumulus is free software: you can redistribute it and/or modify
-// it under the terms of the GNU General Public License as published by
-// the Free Software Foundation, either version 3 of the License, or
-// (at your option) any later version.
-
-// Cumulus is distributed in the hope that it will be useful,
-// but WITHOUT ANY WARRANTY; without even the implied warranty of
-// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-// GNU General Public License for more details.
-
-// You should have received a copy of the GNU General Public License
-// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
-
-//! Autogenerated weights for `pallet_assets`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-11-11, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("indranet-dev"), DB CACHE: 128
-
-// Executed Command:
-// ./target/release/selendra-collator
-// benchmark
-// --chain=indranet-dev
-// --execution=wasm
-// --wasm-execution=compiled
-// --pallet=pallet_assets
-// --extrinsic=*
-// --steps=50
-// --repeat=20
-// --raw
-// --header=./file_header.txt
-// --output=./selendra-parachains/indranet/src/weights
-
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::Weight};
-use sp_std::marker::PhantomData;
-
-/// Weight functions for `pallet_assets`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
-	// Storage: Assets Asset (r:1 w:1)
-	fn create() -> Weight {
-		(38_330_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	fn force_create() -> Weight {
-		(19_968_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:5002 w:5001)
-	// Storage: System Account (r:5000 w:5000)
-	// Storage: Assets Metadata (r:1 w:0)
-	// Storage: Assets Approvals (r:501 w:500)
-	fn destroy(c: u32, s: u32, a: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 39_000
-			.saturating_add((19_128_000 as Weight).saturating_mul(c as Weight))
-			// Standard Error: 39_000
-			.saturating_add((24_393_000 as Weight).saturating_mul(s as Weight))
-			// Standard Error: 399_000
-			.saturating_add((25_025_000 as Weight).saturating_mul(a as Weight))
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
-			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(s as Weight)))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	fn mint() -> Weight {
-		(43_733_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:1 w:1)
-	fn burn() -> Weight {
-		(50_128_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	fn transfer() -> Weight {
-		(74_432_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	fn transfer_keep_alive() -> Weight {
-		(63_009_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	fn force_transfer() -> Weight {
-		(74_436_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(4 as Weight))
-			.saturating_add(T::DbWeight::get().writes(4 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Account (r:1 w:1)
-	fn freeze() -> Weight {
-		(30_055_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Account (r:1 w:1)
-	fn thaw() -> Weight {
-		(29_672_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	fn freeze_asset() -> Weight {
-		(22_516_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	fn thaw_asset() -> Weight {
-		(22_478_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Metadata (r:1 w:0)
-	fn transfer_ownership() -> Weight {
-		(24_701_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	fn set_team() -> Weight {
-		(22_765_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:1)
-	fn set_metadata(_n: u32, s: u32, ) -> Weight {
-		(45_490_000 as Weight)
-			// Standard Error: 0
-			.saturating_add((9_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:1)
-	fn clear_metadata() -> Weight {
-		(44_713_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:1)
-	fn force_set_metadata(_n: u32, s: u32, ) -> Weight {
-		(23_769_000 as Weight)
-			// Standard Error: 0
-			.saturating_add((5_000 as Weight).saturating_mul(s as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:0)
-	// Storage: Assets Metadata (r:1 w:1)
-	fn force_clear_metadata() -> Weight {
-		(44_084_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	fn force_asset_status() -> Weight {
-		(20_952_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Approvals (r:1 w:1)
-	fn approve_transfer() -> Weight {
-		(51_720_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Assets Approvals (r:1 w:1)
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Account (r:2 w:2)
-	// Storage: System Account (r:1 w:1)
-	fn transfer_approved() -> Weight {
-		(96_937_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(5 as Weight))
-			.saturating_add(T::DbWeight::get().writes(5 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Approvals (r:1 w:1)
-	fn cancel_approval() -> Weight {
-		(51_512_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Assets Asset (r:1 w:1)
-	// Storage: Assets Approvals (r:1 w:1)
-	fn force_cancel_approval() -> Weight {
-		(53_759_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-}
@@ -1,142 +0,0 @@
-// Copyright 2021 Parity Technologies (UK) Ltd.
-// This file is part of Cumulus.
-
-// Cumulus is free software: you can redistribute it and/or modify
-// it under the terms of the GNU General Public License as published by
-// the Free Software Foundation, either version 3 of the License, or
-// (at your option) any later version.
-
-// Cumulus is distributed in the hope that it will be useful,
-// but WITHOUT ANY WARRANTY; without even the implied warranty of
-// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-// GNU General Public License for more details.
-
-// You should have received a copy of the GNU General Public License
-// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
-
-//! Autogenerated weights for `pallet_proxy`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-11-11, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("indranet-dev"), DB CACHE: 128
-
-// Executed Command:
-// ./target/release/selendra-collator
-// benchmark
-// --chain=indranet-dev
-// --execution=wasm
-// --wasm-execution=compiled
-// --pallet=pallet_proxy
-// --extrinsic=*
-// --steps=50
-// --repeat=20
-// --raw
-// --header=./file_header.txt
-// --output=./selendra-parachains/indranet/src/weights
-
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::Weight};
-use sp_std::marker::PhantomData;
-
-/// Weight functions for `pallet_proxy`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
-	// Storage: Proxy Proxies (r:1 w:0)
-	fn proxy(p: u32, ) -> Weight {
-		(22_053_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((133_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:0)
-	// Storage: Proxy Announcements (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
-	fn proxy_announced(a: u32, p: u32, ) -> Weight {
-		(48_754_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((451_000 as Weight).saturating_mul(a as Weight))
-			// Standard Error: 2_000
-			.saturating_add((147_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Proxy Announcements (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
-	fn remove_announcement(a: u32, p: u32, ) -> Weight {
-		(32_972_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((458_000 as Weight).saturating_mul(a as Weight))
-			// Standard Error: 1_000
-			.saturating_add((20_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Proxy Announcements (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
-	fn reject_announcement(a: u32, p: u32, ) -> Weight {
-		(33_110_000 as Weight)
-			// Standard Error: 1_000
-			.saturating_add((454_000 as Weight).saturating_mul(a as Weight))
-			// Standard Error: 1_000
-			.saturating_add((18_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:0)
-	// Storage: Proxy Announcements (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
-	fn announce(a: u32, p: u32, ) -> Weight {
-		(46_064_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((449_000 as Weight).saturating_mul(a as Weight))
-			// Standard Error: 2_000
-			.saturating_add((141_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:1)
-	fn add_proxy(p: u32, ) -> Weight {
-		(39_585_000 as Weight)
-			// Standard Error: 4_000
-			.saturating_add((182_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:1)
-	fn remove_proxy(p: u32, ) -> Weight {
-		(32_249_000 as Weight)
-			// Standard Error: 3_000
-			.saturating_add((200_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:1)
-	fn remove_proxies(p: u32, ) -> Weight {
-		(31_543_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((122_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: unknown [0x3a65787472696e7369635f696e646578] (r:1 w:0)
-	// Storage: Proxy Proxies (r:1 w:1)
-	fn anonymous(p: u32, ) -> Weight {
-		(44_425_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((20_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Proxy Proxies (r:1 w:1)
-	fn kill_anonymous(p: u32, ) -> Weight {
-		(33_200_000 as Weight)
-			// Standard Error: 2_000
-			.saturating_add((123_000 as Weight).saturating_mul(p as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-}
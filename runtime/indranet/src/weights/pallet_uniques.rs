@@ -1,219 +0,0 @@
-// Copyright 2021 Parity Technologies (UK) Ltd.
-// This file is part of Cumulus.
-
-// Cumulus is free software: you can redistribute it and/or modify
-// it under the terms of the GNU General Public License as published by
-// the Free Software Foundation, either version 3 of the License, or
-// (at your option) any later version.
-
-// Cumulus is distributed in the hope that it will be useful,
-// but WITHOUT ANY WARRANTY; without even the implied warranty of
-// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-// GNU General Public License for more details.
-
-// You should have received a copy of the GNU General Public License
-// along with Cumulus.  If not, see <http://www.gnu.org/licenses/>.
-
-//! Autogenerated weights for `pallet_uniques`
-//!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2021-11-12, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("indranet-dev"), DB CACHE: 128
-
-// Executed Command:
-// ./target/release/selendra-collator
-// benchmark
-// --chain=indranet-dev
-// --execution=wasm
-// --wasm-execution=compiled
-// --pallet=pallet_uniques
-// --extrinsic=*
-// --steps=50
-// --repeat=20
-// --raw
-// --header=./file_header.txt
-// --output=./selendra-parachains/indranet/src/weights
-
-
-#![cfg_attr(rustfmt, rustfmt_skip)]
-#![allow(unused_parens)]
-#![allow(unused_imports)]
-
-use frame_support::{traits::Get, weights::Weight};
-use sp_std::marker::PhantomData;
-
-/// Weight functions for `pallet_uniques`.
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> pallet_uniques::WeightInfo for WeightInfo<T> {
-	// Storage: Uniques Class (r:1 w:1)
-	fn create() -> Weight {
-		(38_475_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	fn force_create() -> Weight {
-		(19_667_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques Asset (r:1 w:0)
-	// Storage: Uniques Attribute (r:0 w:1000)
-	// Storage: Uniques ClassMetadataOf (r:0 w:1)
-	// Storage: Uniques InstanceMetadataOf (r:0 w:1000)
-	// Storage: Uniques Account (r:0 w:20)
-	fn destroy(n: u32, m: u32, a: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 13_000
-			.saturating_add((14_274_000 as Weight).saturating_mul(n as Weight))
-			// Standard Error: 13_000
-			.saturating_add((1_042_000 as Weight).saturating_mul(m as Weight))
-			// Standard Error: 13_000
-			.saturating_add((911_000 as Weight).saturating_mul(a as Weight))
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(m as Weight)))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(a as Weight)))
-	}
-	// Storage: Uniques Asset (r:1 w:1)
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques Account (r:0 w:1)
-	fn mint() -> Weight {
-		(50_275_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques Asset (r:1 w:1)
-	// Storage: Uniques Account (r:0 w:1)
-	fn burn() -> Weight {
-		(52_374_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:0)
-	// Storage: Uniques Asset (r:1 w:1)
-	// Storage: Uniques Account (r:0 w:2)
-	fn transfer() -> Weight {
-		(38_527_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(3 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques Asset (r:100 w:100)
-	fn redeposit(i: u32, ) -> Weight {
-		(0 as Weight)
-			// Standard Error: 14_000
-			.saturating_add((22_780_000 as Weight).saturating_mul(i as Weight))
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(i as Weight)))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
-	}
-	// Storage: Uniques Asset (r:1 w:1)
-	// Storage: Uniques Class (r:1 w:0)
-	fn freeze() -> Weight {
-		(27_266_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Asset (r:1 w:1)
-	// Storage: Uniques Class (r:1 w:0)
-	fn thaw() -> Weight {
-		(27_465_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	fn freeze_class() -> Weight {
-		(20_629_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	fn thaw_class() -> Weight {
-		(21_183_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: System Account (r:1 w:1)
-	fn transfer_ownership() -> Weight {
-		(45_475_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	fn set_team() -> Weight {
-		(22_060_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	fn force_asset_status() -> Weight {
-		(20_397_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques InstanceMetadataOf (r:1 w:0)
-	// Storage: Uniques Attribute (r:1 w:1)
-	fn set_attribute() -> Weight {
-		(62_915_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques InstanceMetadataOf (r:1 w:0)
-	// Storage: Uniques Attribute (r:1 w:1)
-	fn clear_attribute() -> Weight {
-		(57_451_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques InstanceMetadataOf (r:1 w:1)
-	fn set_metadata() -> Weight {
-		(48_590_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques InstanceMetadataOf (r:1 w:1)
-	fn clear_metadata() -> Weight {
-		(47_455_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:1)
-	// Storage: Uniques ClassMetadataOf (r:1 w:1)
-	fn set_class_metadata() -> Weight {
-		(47_450_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:0)
-	// Storage: Uniques ClassMetadataOf (r:1 w:1)
-	fn clear_class_metadata() -> Weight {
-		(43_338_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:0)
-	// Storage: Uniques Asset (r:1 w:1)
-	fn approve_transfer() -> Weight {
-		(29_773_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-	// Storage: Uniques Class (r:1 w:0)
-	// Storage: Uniques Asset (r:1 w:1)
-	fn cancel_approval() -> Weight {
-		(29_838_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
-	}
-}
@@ -18,11 +18,13 @@
 
 pub mod types;
 
+mod debug;
 mod eth;
 mod eth_pubsub;
 mod net;
 mod web3;
 
+pub use debug::{DebugApi, DebugApiServer};
 pub use eth::{EthApi, EthApiServer, EthFilterApi, EthFilterApiServer};
 pub use eth_pubsub::{EthPubSubApi, EthPubSubApiServer};
 pub use net::{NetApi, NetApiServer};
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2015-2020 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::Bytes;
+use ethereum_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `debug_traceTransaction` and `debug_traceBlockByNumber` options
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceParams {
+	/// The tracer to use, `callTracer` or none for the struct logger.
+	pub tracer: Option<String>,
+	pub disable_storage: Option<bool>,
+	pub disable_memory: Option<bool>,
+	pub disable_stack: Option<bool>,
+}
+
+/// The trace of a transaction, in the format of its tracer
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Trace {
+	Raw(RawTrace),
+	Call(CallTrace),
+}
+
+/// The struct logger trace of a transaction
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawTrace {
+	/// Gas used by the transaction.
+	pub gas: U256,
+	pub failed: bool,
+	pub return_value: Bytes,
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// The state of the EVM before it executed an opcode
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: String,
+	/// Gas left.
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<H256>>,
+	/// Memory, in 32 byte words.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<H256>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// A call frame of the `callTracer` trace of a transaction
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallTrace {
+	/// The opcode of the call, such as `CALL` or `CREATE2`.
+	#[serde(rename = "type")]
+	pub call_type: &'static str,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Bytes,
+	pub output: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallTrace>,
+}
+
+/// The trace of a transaction of a block
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransactionTrace {
+	pub tx_hash: H256,
+	pub result: Trace,
+}
@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use crate::{frontier_backend_client, internal_err};
+use ethereum_types::H256;
+use evm::{ExitError, ExitReason};
+use fc_rpc_core::{
+	types::{
+		BlockNumber, BlockTransactionTrace, Bytes, CallTrace, RawTrace, StructLog, Trace,
+		TraceParams,
+	},
+	DebugApi as DebugApiT,
+};
+use fp_evm::{CallType, TracerConfig, TransactionTrace};
+use fp_rpc::{DebugRuntimeApi, EthereumRuntimeRPCApi};
+use jsonrpc_core::{Error, Result};
+use sc_client_api::{backend::Backend, BlockBackend};
+use sc_rpc::DenyUnsafe;
+use sp_api::{ApiExt, BlockId, Core, HeaderT, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{marker::PhantomData, sync::Arc};
+
+pub use fc_rpc_core::DebugApiServer;
+
+pub struct DebugApi<B: BlockT, C, BE> {
+	client: Arc<C>,
+	backend: Arc<fc_db::Backend<B>>,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<BE>,
+}
+
+impl<B: BlockT, C, BE> DebugApi<B, C, BE> {
+	pub fn new(client: Arc<C>, backend: Arc<fc_db::Backend<B>>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, backend, deny_unsafe, _marker: PhantomData }
+	}
+}
+
+impl<B, C, BE> DebugApi<B, C, BE>
+where
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + 'static,
+	C::Api: DebugRuntimeApi<B>,
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+{
+	/// The header and extrinsics of a block, and the id of its parent to re-execute them on.
+	fn block_to_trace(&self, id: BlockId<B>) -> Result<(B::Header, Vec<B::Extrinsic>, BlockId<B>)> {
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("fetch header failed: {:?}", err)))?
+			.ok_or_else(|| internal_err("block not found"))?;
+		let extrinsics = self
+			.client
+			.block_body(&id)
+			.map_err(|err| internal_err(format!("fetch block body failed: {:?}", err)))?
+			.ok_or_else(|| internal_err("block not found"))?;
+		let parent = BlockId::Hash(*header.parent_hash());
+
+		let has_api = self
+			.client
+			.runtime_api()
+			.has_api::<dyn DebugRuntimeApi<B>>(&parent)
+			.map_err(|err| internal_err(format!("fetch runtime api failed: {:?}", err)))?;
+		if !has_api {
+			return Err(internal_err(
+				"runtime does not support tracing, override it with a tracing build of the same \
+				 version through `--wasm-runtime-overrides`",
+			));
+		}
+
+		Ok((header, extrinsics, parent))
+	}
+}
+
+impl<B, C, BE> DebugApiT for DebugApi<B, C, BE>
+where
+	C: ProvideRuntimeApi<B> + BlockBackend<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: DebugRuntimeApi<B> + EthereumRuntimeRPCApi<B>,
+	BE: Backend<B> + 'static,
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+{
+	fn trace_transaction(&self, hash: H256, params: Option<TraceParams>) -> Result<Trace> {
+		self.deny_unsafe.check_if_safe()?;
+		let config = tracer_config(params)?;
+
+		let (ethereum_block_hash, _) = frontier_backend_client::load_transactions::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			hash,
+			true,
+		)?
+		.ok_or_else(|| internal_err("transaction not found"))?;
+		let id =
+			frontier_backend_client::load_hash::<B>(self.backend.as_ref(), ethereum_block_hash)?
+				.ok_or_else(|| internal_err("block not found"))?;
+		let (header, extrinsics, parent) = self.block_to_trace(id)?;
+
+		let api = self.client.runtime_api();
+		api.initialize_block(&parent, &header)
+			.map_err(|err| internal_err(format!("initialize block failed: {:?}", err)))?;
+		let trace = api
+			.trace_transaction(&parent, extrinsics, hash, config)
+			.map_err(|err| internal_err(format!("runtime trace call failed: {:?}", err)))?
+			.map_err(|err| internal_err(format!("trace transaction failed: {:?}", err)))?;
+
+		Ok(format_trace(trace))
+	}
+
+	fn trace_block_by_number(
+		&self,
+		number: BlockNumber,
+		params: Option<TraceParams>,
+	) -> Result<Vec<BlockTransactionTrace>> {
+		self.deny_unsafe.check_if_safe()?;
+		let config = tracer_config(params)?;
+
+		let id = frontier_backend_client::native_block_id::<B, C>(
+			self.client.as_ref(),
+			self.backend.as_ref(),
+			Some(number),
+		)?
+		.ok_or_else(|| internal_err("pending block cannot be traced"))?;
+		let (header, extrinsics, parent) = self.block_to_trace(id)?;
+
+		let api = self.client.runtime_api();
+		let transaction_hashes = api
+			.extrinsic_filter(&parent, extrinsics.clone())
+			.map_err(|err| {
+				internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
+			})?
+			.into_iter()
+			.map(|transaction| transaction.hash());
+		api.initialize_block(&parent, &header)
+			.map_err(|err| internal_err(format!("initialize block failed: {:?}", err)))?;
+		let traces = api
+			.trace_block(&parent, extrinsics, config)
+			.map_err(|err| internal_err(format!("runtime trace call failed: {:?}", err)))?
+			.map_err(|err| internal_err(format!("trace block failed: {:?}", err)))?;
+
+		Ok(transaction_hashes
+			.zip(traces)
+			.map(|(tx_hash, trace)| BlockTransactionTrace { tx_hash, result: format_trace(trace) })
+			.collect())
+	}
+}
+
+fn tracer_config(params: Option<TraceParams>) -> Result<TracerConfig> {
+	let params = params.unwrap_or_default();
+	match params.tracer.as_deref() {
+		None => Ok(TracerConfig::Raw {
+			disable_storage: params.disable_storage.unwrap_or(false),
+			disable_memory: params.disable_memory.unwrap_or(false),
+			disable_stack: params.disable_stack.unwrap_or(false),
+		}),
+		Some("callTracer") => Ok(TracerConfig::CallTracer),
+		Some(tracer) => Err(Error::invalid_params(format!("unsupported tracer {}", tracer))),
+	}
+}
+
+fn format_trace(trace: TransactionTrace) -> Trace {
+	match trace {
+		TransactionTrace::Raw(trace) => Trace::Raw(RawTrace {
+			gas: trace.gas_used,
+			failed: !matches!(trace.exit_reason, Some(ExitReason::Succeed(_))),
+			return_value: Bytes(trace.return_value),
+			struct_logs: trace
+				.step_logs
+				.into_iter()
+				.map(|step| StructLog {
+					pc: step.pc,
+					op: opcode_name(step.opcode),
+					gas: step.gas,
+					gas_cost: step.gas_cost,
+					depth: step.depth,
+					stack: step.stack,
+					memory: step.memory.map(|memory| {
+						memory
+							.chunks(32)
+							.map(|chunk| {
+								let mut word = H256::zero();
+								word[..chunk.len()].copy_from_slice(chunk);
+								word
+							})
+							.collect()
+					}),
+					storage: step.storage,
+				})
+				.collect(),
+		}),
+		TransactionTrace::CallTracer(trace) => Trace::Call(format_call(trace)),
+	}
+}
+
+fn format_call(call: fp_evm::CallTrace) -> CallTrace {
+	CallTrace {
+		call_type: match call.call_type {
+			CallType::Call => "CALL",
+			CallType::CallCode => "CALLCODE",
+			CallType::DelegateCall => "DELEGATECALL",
+			CallType::StaticCall => "STATICCALL",
+			CallType::Create => "CREATE",
+			CallType::Create2 => "CREATE2",
+			CallType::SelfDestruct => "SELFDESTRUCT",
+		},
+		from: call.from,
+		to: call.to,
+		value: call.value,
+		gas: call.gas,
+		gas_used: call.gas_used,
+		input: Bytes(call.input),
+		output: Bytes(call.output),
+		error: match call.exit_reason {
+			None | Some(ExitReason::Succeed(_)) => None,
+			Some(ExitReason::Revert(_)) => Some("execution reverted".to_string()),
+			Some(ExitReason::Error(ExitError::OutOfGas)) => Some("out of gas".to_string()),
+			Some(ExitReason::Error(err)) => Some(format!("{:?}", err)),
+			Some(ExitReason::Fatal(err)) => Some(format!("{:?}", err)),
+		},
+		calls: call.calls.into_iter().map(format_call).collect(),
+	}
+}
+
+fn opcode_name(opcode: u8) -> String {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "SHA3",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode {:#04x} not defined", opcode),
+	};
+	name.to_string()
+}
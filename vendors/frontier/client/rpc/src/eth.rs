@@ -2341,45 +2341,14 @@ where
 				if rewards.len() > 0 {
 					response.reward = Some(rewards);
 				}
-				// Calculate next base fee.
-				if let (Some(last_gas_used), Some(last_fee_per_gas)) = (
-					response.gas_used_ratio.last(),
-					response.base_fee_per_gas.last(),
-				) {
-					let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(
+				// The next base fee is the one pallet-base-fee computed when finalizing the newest
+				// block, from the block's fullness, the elasticity and the runtime's thresholds.
+				if !response.base_fee_per_gas.is_empty() {
+					response.base_fee_per_gas.push(base_fee_after::<B, C, BE>(
 						self.client.as_ref(),
+						self.overrides.as_ref(),
 						id,
-					);
-					let handler = self
-						.overrides
-						.schemas
-						.get(&schema)
-						.unwrap_or(&self.overrides.fallback);
-					let default_elasticity = sp_runtime::Permill::from_parts(125_000);
-					let elasticity = handler
-						.elasticity(&id)
-						.unwrap_or(default_elasticity)
-						.deconstruct();
-					let elasticity = elasticity as f64 / 1_000_000f64;
-					let last_fee_per_gas = last_fee_per_gas.as_u64() as f64;
-					if last_gas_used > &0.5 {
-						// Increase base gas
-						let increase = ((last_gas_used - 0.5) * 2f64) * elasticity;
-						let new_base_fee =
-							(last_fee_per_gas + (last_fee_per_gas * increase)) as u64;
-						response.base_fee_per_gas.push(U256::from(new_base_fee));
-					} else if last_gas_used < &0.5 {
-						// Decrease base gas
-						let increase = ((0.5 - last_gas_used) * 2f64) * elasticity;
-						let new_base_fee =
-							(last_fee_per_gas - (last_fee_per_gas * increase)) as u64;
-						response.base_fee_per_gas.push(U256::from(new_base_fee));
-					} else {
-						// Same base gas
-						response
-							.base_fee_per_gas
-							.push(U256::from(last_fee_per_gas as u64));
-					}
+					));
 				}
 				return Ok(response);
 			} else {
@@ -2931,15 +2900,13 @@ where
 		fee_history_cache: FeeHistoryCache,
 		block_limit: u64,
 	) {
-		use sp_runtime::Permill;
-
 		struct TransactionHelper {
 			gas_used: u64,
 			effective_reward: u64,
 		}
 		// Calculates the cache for a single block
 		#[rustfmt::skip]
-		let fee_history_cache_item = |hash: H256, elasticity: Permill| -> (
+		let fee_history_cache_item = |hash: H256| -> (
 			FeeHistoryCacheItem,
 			Option<u64>
 		) {
@@ -2969,10 +2936,14 @@ where
 
 			let block = handler.current_block(&id);
 			let mut block_number: Option<u64> = None;
-			let base_fee = if let Some(base_fee) = handler.base_fee(&id) {
-				base_fee
-			} else {
-				client.runtime_api().gas_price(&id).unwrap_or(U256::zero())
+			// The base fee paid in a block is the one its parent left in storage.
+			let base_fee = match client.header(id) {
+				Ok(Some(header)) => base_fee_after::<B, C, BE>(
+					client.as_ref(),
+					overrides.as_ref(),
+					BlockId::Hash(*header.parent_hash()),
+				),
+				_ => U256::zero(),
 			};
 			let receipts = handler.current_receipts(&id);
 			let mut result = FeeHistoryCacheItem {
@@ -2982,17 +2953,10 @@ where
 			};
 			if let (Some(block), Some(receipts)) = (block, receipts) {
 				block_number = Some(block.header.number.as_u64());
-				// Calculate the gas used ratio.
-				// TODO this formula needs the pallet-base-fee configuration.
-				// By now we assume just the default 0.125 (elasticity multiplier 8).
+				// Calculate the gas used ratio, as defined by EIP-1559.
 				let gas_used = block.header.gas_used.as_u64() as f64;
 				let gas_limit = block.header.gas_limit.as_u64() as f64;
-				let elasticity_multiplier: f64 = (elasticity / Permill::from_parts(1_000_000))
-					.deconstruct()
-					.into();
-				let gas_target = gas_limit / elasticity_multiplier;
-
-				result.gas_used_ratio = gas_used / (gas_target * elasticity_multiplier);
+				result.gas_used_ratio = if gas_limit > 0f64 { gas_used / gas_limit } else { 0f64 };
 
 				let mut previous_cumulative_gas = U256::zero();
 				let used_gas = |current: U256, previous: &mut U256| -> u64 {
@@ -3073,18 +3037,6 @@ where
 		while let Some(notification) = notification_st.next().await {
 			if notification.is_new_best {
 				let hash = notification.hash;
-				let id = BlockId::Hash(hash);
-				let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(
-					client.as_ref(),
-					id,
-				);
-				let handler = overrides
-					.schemas
-					.get(&schema)
-					.unwrap_or(&overrides.fallback);
-
-				let default_elasticity = Permill::from_parts(125_000);
-				let elasticity = handler.elasticity(&id).unwrap_or(default_elasticity);
 				// In case a re-org happened on import.
 				if let Some(tree_route) = notification.tree_route {
 					if let Ok(fee_history_cache) = &mut fee_history_cache.lock() {
@@ -3098,19 +3050,39 @@ where
 						// Insert enacted.
 						let _ = tree_route.enacted().iter().map(|hash_and_number| {
 							let (result, block_number) =
-								fee_history_cache_item(hash_and_number.hash, elasticity);
+								fee_history_cache_item(hash_and_number.hash);
 							commit_if_any(result, block_number);
 						});
 					}
 				}
 				// Cache the imported block.
-				let (result, block_number) = fee_history_cache_item(hash, elasticity);
+				let (result, block_number) = fee_history_cache_item(hash);
 				commit_if_any(result, block_number);
 			}
 		}
 	}
 }
 
+/// The base fee per gas of the block built on top of `id`, as stored by pallet-base-fee when
+/// finalizing `id`, or the runtime's gas price for runtimes without it.
+fn base_fee_after<B, C, BE>(client: &C, overrides: &OverrideHandle<B>, id: BlockId<B>) -> U256
+where
+	B: BlockT<Hash = H256> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + StorageProvider<B, BE> + Send + Sync + 'static,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	BE: Backend<B> + 'static,
+	BE::State: StateBackend<BlakeTwo256>,
+{
+	let schema = frontier_backend_client::onchain_storage_schema::<B, C, BE>(client, id);
+	let handler = overrides
+		.schemas
+		.get(&schema)
+		.unwrap_or(&overrides.fallback);
+	handler
+		.base_fee(&id)
+		.unwrap_or_else(|| client.runtime_api().gas_price(&id).unwrap_or_default())
+}
+
 /// Stores an LRU cache for block data and their transaction statuses.
 /// These are large and take a lot of time to fetch from the database.
 /// Storing them in an LRU cache will allow to reduce database accesses
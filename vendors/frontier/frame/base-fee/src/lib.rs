@@ -38,6 +38,8 @@ pub mod pallet {
 		type Threshold: BaseFeeThreshold;
 		type IsActive: Get<bool>;
 		type DefaultBaseFeePerGas: Get<U256>;
+		/// The origin allowed to change the `Elasticity`.
+		type SetElasticityOrigin: EnsureOrigin<Self::Origin>;
 	}
 
 	#[pallet::pallet]
@@ -82,6 +84,7 @@ pub mod pallet {
 		fn build(&self) {
 			<BaseFeePerGas<T>>::put(self.base_fee_per_gas);
 			<IsActive<T>>::put(self.is_active);
+			<Elasticity<T>>::put(self.elasticity);
 		}
 	}
 
@@ -226,7 +229,7 @@ pub mod pallet {
 
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
 		pub fn set_elasticity(origin: OriginFor<T>, elasticity: Permill) -> DispatchResult {
-			ensure_root(origin)?;
+			T::SetElasticityOrigin::ensure_origin(origin)?;
 			<Elasticity<T>>::put(elasticity);
 			Self::deposit_event(Event::NewElasticity(elasticity));
 			Ok(())
@@ -246,7 +249,7 @@ mod tests {
 	use crate as pallet_base_fee;
 
 	use frame_support::{
-		assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnFinalize,
+		assert_noop, assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnFinalize,
 		weights::DispatchClass,
 	};
 	use sp_core::{H256, U256};
@@ -254,7 +257,7 @@ mod tests {
 	use sp_runtime::{
 		testing::Header,
 		traits::{BlakeTwo256, IdentityLookup},
-		Permill,
+		DispatchError, Permill,
 	};
 
 	pub fn new_test_ext(base_fee: Option<U256>) -> TestExternalities {
@@ -335,6 +338,7 @@ mod tests {
 		type Threshold = BaseFeeThreshold;
 		type IsActive = IsActive;
 		type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+		type SetElasticityOrigin = frame_system::EnsureRoot<u64>;
 	}
 
 	frame_support::construct_runtime!(
@@ -487,4 +491,31 @@ mod tests {
 			assert_eq!(BaseFee::elasticity(), Permill::from_parts(1_000));
 		});
 	}
+
+	#[test]
+	fn set_elasticity_requires_origin() {
+		let base_fee = U256::from(1_000_000_000);
+		new_test_ext(Some(base_fee)).execute_with(|| {
+			assert_noop!(
+				BaseFee::set_elasticity(Origin::signed(1), Permill::from_parts(1_000)),
+				DispatchError::BadOrigin
+			);
+			assert_eq!(BaseFee::elasticity(), Permill::from_parts(125_000));
+		});
+	}
+
+	#[test]
+	fn genesis_sets_elasticity() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_base_fee::GenesisConfig::<Test>::new(
+			U256::from(1_000_000_000),
+			true,
+			Permill::from_parts(250_000),
+		)
+		.assimilate_storage(&mut t)
+		.unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			assert_eq!(BaseFee::elasticity(), Permill::from_parts(250_000));
+		});
+	}
 }
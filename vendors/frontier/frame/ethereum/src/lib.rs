@@ -713,6 +713,11 @@ impl<T: Config> Pallet<T> {
 		CurrentReceipts::<T>::get()
 	}
 
+	/// Get the transactions applied so far in the block being built, with their receipts.
+	pub fn pending() -> Vec<(Transaction, TransactionStatus, Receipt)> {
+		Pending::<T>::get()
+	}
+
 	/// Execute an Ethereum transaction.
 	pub fn execute(
 		from: H160,
@@ -58,6 +58,8 @@ mod mock;
 pub mod runner;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
 pub mod benchmarks;
@@ -65,8 +67,9 @@ pub mod benchmarks;
 pub use crate::runner::Runner;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use fp_evm::{
-	Account, CallInfo, CreateInfo, ExecutionInfo, LinearCostPrecompile, Log, Precompile,
-	PrecompileFailure, PrecompileOutput, PrecompileResult, PrecompileSet, Vicinity,
+	Account, CallInfo, CallTrace, CallType, CreateInfo, ExecutionInfo, LinearCostPrecompile, Log,
+	Precompile, PrecompileFailure, PrecompileOutput, PrecompileResult, PrecompileSet, RawTrace,
+	StepLog, TracerConfig, TransactionTrace, Vicinity,
 };
 
 #[cfg(feature = "std")]
@@ -663,15 +666,17 @@ pub trait OnChargeEVMTransaction<T: Config> {
 
 	/// After the transaction was executed the actual fee can be calculated.
 	/// This function should refund any overpaid fees and optionally deposit
-	/// the corrected amount.
+	/// the `base_fee` part of the corrected amount. The rest of it is the
+	/// priority tip, which is returned.
 	fn correct_and_deposit_fee(
 		who: &H160,
 		corrected_fee: U256,
+		base_fee: U256,
 		already_withdrawn: Self::LiquidityInfo,
-	);
+	) -> Self::LiquidityInfo;
 
 	/// Introduced in EIP1559 to handle the priority tip payment to the block Author.
-	fn pay_priority_fee(tip: U256);
+	fn pay_priority_fee(tip: Self::LiquidityInfo);
 }
 
 /// Implements the transaction payment for a pallet implementing the `Currency`
@@ -712,8 +717,9 @@ where
 	fn correct_and_deposit_fee(
 		who: &H160,
 		corrected_fee: U256,
+		base_fee: U256,
 		already_withdrawn: Self::LiquidityInfo,
-	) {
+	) -> Self::LiquidityInfo {
 		if let Some(paid) = already_withdrawn {
 			let account_id = T::AddressMapping::into_account_id(*who);
 
@@ -750,13 +756,20 @@ where
 				.offset(refund_imbalance)
 				.same()
 				.unwrap_or_else(|_| C::NegativeImbalance::zero());
-			OU::on_unbalanced(adjusted_paid);
+			// Only the base fee is handled here, the tip is paid separately.
+			let (base_fee, tip) = adjusted_paid.split(base_fee.low_u128().unique_saturated_into());
+			OU::on_unbalanced(base_fee);
+			return Some(tip);
 		}
+		None
 	}
 
-	fn pay_priority_fee(tip: U256) {
-		let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
-		let _ = C::deposit_into_existing(&account_id, tip.low_u128().unique_saturated_into());
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		if let Some(tip) = tip {
+			let account_id = T::AddressMapping::into_account_id(<Pallet<T>>::find_author());
+			// If the author can't receive it, the tip is dropped and so burned.
+			let _ = C::resolve_into_existing(&account_id, tip);
+		}
 	}
 }
 
@@ -781,12 +794,13 @@ impl<T> OnChargeEVMTransaction<T> for ()
 	fn correct_and_deposit_fee(
 		who: &H160,
 		corrected_fee: U256,
+		base_fee: U256,
 		already_withdrawn: Self::LiquidityInfo,
-	) {
-		<EVMCurrencyAdapter::<<T as Config>::Currency, ()> as OnChargeEVMTransaction<T>>::correct_and_deposit_fee(who, corrected_fee, already_withdrawn)
+	) -> Self::LiquidityInfo {
+		<EVMCurrencyAdapter::<<T as Config>::Currency, ()> as OnChargeEVMTransaction<T>>::correct_and_deposit_fee(who, corrected_fee, base_fee, already_withdrawn)
 	}
 
-	fn pay_priority_fee(tip: U256) {
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
 		<EVMCurrencyAdapter::<<T as Config>::Currency, ()> as OnChargeEVMTransaction<T>>::pay_priority_fee(tip);
 	}
 }
@@ -126,9 +126,10 @@ fn fee_deduction() {
 		let imbalance = <<Test as Config>::OnChargeTransaction as OnChargeEVMTransaction<Test>>::withdraw_fee(&evm_addr, U256::from(10)).unwrap();
 		assert_eq!(Balances::free_balance(&substrate_addr), 90);
 
-		// Refund fees as 5 units
-		<<Test as Config>::OnChargeTransaction as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(&evm_addr, U256::from(5), imbalance);
+		// Refund fees as 5 units, 2 of which are the tip
+		let tip = <<Test as Config>::OnChargeTransaction as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(&evm_addr, U256::from(5), U256::from(3), imbalance);
 		assert_eq!(Balances::free_balance(&substrate_addr), 95);
+		assert_eq!(tip.map(|tip| tip.peek()), Some(2));
 	});
 }
 
@@ -354,3 +355,76 @@ fn handle_sufficient_reference() {
 		assert_eq!(account_2.sufficients, 0);
 	});
 }
+
+#[cfg(feature = "tracing")]
+fn traced_call(target: H160, config: TracerConfig) -> TransactionTrace {
+	let (result, trace) = crate::tracing::trace(config, || {
+		EVM::call(
+			Origin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+		)
+	});
+	assert_ok!(result);
+	trace
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn raw_tracer_logs_each_step() {
+	new_test_ext().execute_with(|| {
+		let config = TracerConfig::Raw {
+			disable_storage: false,
+			disable_memory: true,
+			disable_stack: false,
+		};
+		let target = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+
+		match traced_call(target, config) {
+			TransactionTrace::Raw(trace) => {
+				assert_eq!(trace.exit_reason, Some(ExitReason::Succeed(ExitSucceed::Stopped)));
+				assert_eq!(trace.step_logs.len(), 1);
+				let step = &trace.step_logs[0];
+				assert_eq!((step.pc, step.opcode, step.depth), (0, 0x00, 1));
+				assert_eq!(step.stack, Some(Vec::new()));
+				assert_eq!(step.memory, None);
+			}
+			trace => panic!("unexpected trace {:?}", trace),
+		}
+	});
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn call_tracer_records_nested_calls() {
+	new_test_ext().execute_with(|| {
+		let caller = H160::from_str("1000000000000000000000000000000000000003").unwrap();
+		let callee = H160::from_str("1000000000000000000000000000000000000001").unwrap();
+		// CALL the callee with no value, input nor output, and all the gas left.
+		let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+		code.extend_from_slice(callee.as_bytes());
+		code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+		EVM::create_account(caller, code);
+
+		match traced_call(caller, TracerConfig::CallTracer) {
+			TransactionTrace::CallTracer(trace) => {
+				assert_eq!(trace.call_type, CallType::Call);
+				assert_eq!((trace.from, trace.to), (H160::default(), caller));
+				assert_eq!(trace.calls.len(), 1);
+				let call = &trace.calls[0];
+				assert_eq!(call.call_type, CallType::Call);
+				assert_eq!((call.from, call.to), (caller, callee));
+				assert_eq!(call.exit_reason, Some(ExitReason::Succeed(ExitSucceed::Stopped)));
+				assert!(call.calls.is_empty());
+			}
+			trace => panic!("unexpected trace {:?}", trace),
+		}
+	});
+}
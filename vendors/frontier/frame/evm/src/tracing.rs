@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracers recording the EVM execution of a transaction, from the events of the `evm` crates.
+
+use crate::{CallTrace, CallType, RawTrace, StepLog, TracerConfig, TransactionTrace};
+use evm::{
+	tracing::{Event as EvmEvent, EventListener as EvmListener},
+	CreateScheme, ExitReason,
+};
+use evm_gasometer::tracing::{
+	Event as GasometerEvent, EventListener as GasometerListener, Snapshot,
+};
+use evm_runtime::tracing::{Event as RuntimeEvent, EventListener as RuntimeListener};
+use sp_core::{H160, H256};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, mem, rc::Rc, vec::Vec};
+
+const CALLCODE: u8 = 0xf2;
+const DELEGATECALL: u8 = 0xf4;
+const STATICCALL: u8 = 0xfa;
+
+/// Run `f`, tracing the EVM execution of the transaction it applies with `config`.
+///
+/// The gas used by the whole transaction isn't known to the EVM, and is left for the caller to
+/// set.
+pub fn trace<R>(config: TracerConfig, f: impl FnOnce() -> R) -> (R, TransactionTrace) {
+	let tracer = Rc::new(RefCell::new(Tracer::new(config)));
+	let mut evm_listener = Listener(tracer.clone());
+	let mut runtime_listener = Listener(tracer.clone());
+	let mut gasometer_listener = Listener(tracer.clone());
+
+	let result = evm::tracing::using(&mut evm_listener, || {
+		evm_runtime::tracing::using(&mut runtime_listener, || {
+			evm_gasometer::tracing::using(&mut gasometer_listener, f)
+		})
+	});
+
+	let trace = tracer.borrow_mut().finish();
+	(result, trace)
+}
+
+/// Forwards the events of each `evm` crate to the shared tracer.
+struct Listener(Rc<RefCell<Tracer>>);
+
+impl EvmListener for Listener {
+	fn event(&mut self, event: EvmEvent) {
+		self.0.borrow_mut().on_evm_event(event)
+	}
+}
+
+impl RuntimeListener for Listener {
+	fn event(&mut self, event: RuntimeEvent) {
+		self.0.borrow_mut().on_runtime_event(event)
+	}
+}
+
+impl GasometerListener for Listener {
+	fn event(&mut self, event: GasometerEvent) {
+		self.0.borrow_mut().on_gasometer_event(event)
+	}
+}
+
+/// A call frame being executed.
+struct Frame {
+	call: CallTrace,
+	/// The last state of the frame's gasometer.
+	snapshot: Option<Snapshot>,
+}
+
+struct Tracer {
+	config: TracerConfig,
+	/// The frames being executed, innermost last.
+	frames: Vec<Frame>,
+	/// The outermost frame, once it exited.
+	root: Option<CallTrace>,
+	/// Whether the transaction just opened the outermost frame, which the executor announces
+	/// again as a call or create.
+	transact_pending: bool,
+	/// The opcode of the last step, which tells what kind of call the next frame is.
+	last_opcode: Option<u8>,
+	steps: Vec<StepLog>,
+	/// The step waiting for the gasometer to record its cost.
+	pending_step: Option<usize>,
+	/// The storage read or written so far, by contract.
+	storage: BTreeMap<H160, BTreeMap<H256, H256>>,
+}
+
+impl Tracer {
+	fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			frames: Vec::new(),
+			root: None,
+			transact_pending: false,
+			last_opcode: None,
+			steps: Vec::new(),
+			pending_step: None,
+			storage: BTreeMap::new(),
+		}
+	}
+
+	fn finish(&mut self) -> TransactionTrace {
+		let root = self.root.take().unwrap_or_default();
+		match self.config {
+			TracerConfig::Raw { .. } => TransactionTrace::Raw(RawTrace {
+				gas_used: root.gas_used,
+				exit_reason: root.exit_reason,
+				return_value: root.output,
+				step_logs: mem::take(&mut self.steps),
+			}),
+			TracerConfig::CallTracer => TransactionTrace::CallTracer(root),
+		}
+	}
+
+	fn on_evm_event(&mut self, event: EvmEvent) {
+		match event {
+			EvmEvent::TransactCall { caller, address, value, data, gas_limit } => self
+				.enter_transaction(CallTrace {
+					call_type: CallType::Call,
+					from: caller,
+					to: address,
+					value,
+					gas: gas_limit.into(),
+					input: data.to_vec(),
+					..Default::default()
+				}),
+			EvmEvent::TransactCreate { caller, value, init_code, gas_limit, address } => self
+				.enter_transaction(CallTrace {
+					call_type: CallType::Create,
+					from: caller,
+					to: address,
+					value,
+					gas: gas_limit.into(),
+					input: init_code.to_vec(),
+					..Default::default()
+				}),
+			EvmEvent::TransactCreate2 { caller, value, init_code, gas_limit, address, .. } => self
+				.enter_transaction(CallTrace {
+					call_type: CallType::Create2,
+					from: caller,
+					to: address,
+					value,
+					gas: gas_limit.into(),
+					input: init_code.to_vec(),
+					..Default::default()
+				}),
+			EvmEvent::Call { code_address, input, target_gas, context, .. } => {
+				if mem::take(&mut self.transact_pending) {
+					return;
+				}
+				// The calls running in the frame of the calling contract have it as their address,
+				// and the others as their caller.
+				let (call_type, from) = match self.last_opcode {
+					Some(CALLCODE) => (CallType::CallCode, context.address),
+					Some(DELEGATECALL) => (CallType::DelegateCall, context.address),
+					Some(STATICCALL) => (CallType::StaticCall, context.caller),
+					_ => (CallType::Call, context.caller),
+				};
+				self.enter(CallTrace {
+					call_type,
+					from,
+					to: code_address,
+					value: context.apparent_value,
+					gas: target_gas.unwrap_or_default().into(),
+					input: input.to_vec(),
+					..Default::default()
+				})
+			},
+			EvmEvent::Create { caller, address, scheme, value, init_code, target_gas } => {
+				if mem::take(&mut self.transact_pending) {
+					return;
+				}
+				let call_type = match scheme {
+					CreateScheme::Create2 { .. } => CallType::Create2,
+					_ => CallType::Create,
+				};
+				self.enter(CallTrace {
+					call_type,
+					from: caller,
+					to: address,
+					value,
+					gas: target_gas.unwrap_or_default().into(),
+					input: init_code.to_vec(),
+					..Default::default()
+				})
+			},
+			EvmEvent::Suicide { address, target, balance } => {
+				if let Some(frame) = self.frames.last_mut() {
+					frame.call.calls.push(CallTrace {
+						call_type: CallType::SelfDestruct,
+						from: address,
+						to: target,
+						value: balance,
+						..Default::default()
+					});
+				}
+			},
+			EvmEvent::Exit { reason, return_value } => {
+				self.exit(reason.clone(), return_value.to_vec())
+			},
+		}
+	}
+
+	fn on_runtime_event(&mut self, event: RuntimeEvent) {
+		match event {
+			RuntimeEvent::Step { context, opcode, position, stack, memory } => {
+				self.transact_pending = false;
+				self.last_opcode = Some(opcode.0);
+				if let TracerConfig::Raw { disable_storage, disable_memory, disable_stack } =
+					self.config
+				{
+					let storage = &self.storage;
+					self.pending_step = Some(self.steps.len());
+					self.steps.push(StepLog {
+						pc: position.as_ref().map_or(0, |pc| *pc as u64),
+						opcode: opcode.0,
+						depth: self.frames.len() as u32,
+						stack: (!disable_stack).then(|| stack.data().clone()),
+						memory: (!disable_memory).then(|| memory.data().clone()),
+						storage: (!disable_storage)
+							.then(|| storage.get(&context.address).cloned().unwrap_or_default()),
+						..Default::default()
+					});
+				}
+			},
+			RuntimeEvent::SLoad { address, index, value }
+			| RuntimeEvent::SStore { address, index, value } => {
+				if let TracerConfig::Raw { disable_storage: false, .. } = self.config {
+					let storage = self.storage.entry(address).or_default();
+					storage.insert(index, value);
+					if let Some(step) = self.steps.last_mut() {
+						step.storage = Some(storage.clone());
+					}
+				}
+			},
+			RuntimeEvent::StepResult { .. } => (),
+		}
+	}
+
+	fn on_gasometer_event(&mut self, event: GasometerEvent) {
+		let previous = self.frames.last().and_then(|frame| frame.snapshot);
+		let (cost, snapshot) = match event {
+			GasometerEvent::RecordCost { cost, snapshot } => (Some(cost), snapshot),
+			GasometerEvent::RecordDynamicCost { gas_cost, memory_gas, snapshot, .. } => {
+				// The memory cost is the total of the frame, of which the step pays the increase.
+				let memory_cost =
+					memory_gas.saturating_sub(previous.map_or(0, |snapshot| snapshot.memory_gas));
+				(Some(gas_cost.saturating_add(memory_cost)), snapshot)
+			},
+			GasometerEvent::RecordRefund { snapshot, .. }
+			| GasometerEvent::RecordStipend { snapshot, .. }
+			| GasometerEvent::RecordTransaction { snapshot, .. } => (None, snapshot),
+		};
+
+		if let Some(cost) = cost {
+			if let Some(step) = self.pending_step.take().and_then(|i| self.steps.get_mut(i)) {
+				step.gas_cost = cost;
+				step.gas = snapshot.map_or(0, |snapshot| gas_left(&snapshot).saturating_add(cost));
+			}
+		}
+		if let (Some(frame), Some(snapshot)) = (self.frames.last_mut(), snapshot) {
+			frame.snapshot = Some(snapshot);
+		}
+	}
+
+	fn enter_transaction(&mut self, call: CallTrace) {
+		self.transact_pending = true;
+		self.enter(call);
+	}
+
+	fn enter(&mut self, call: CallTrace) {
+		self.frames.push(Frame { call, snapshot: None });
+	}
+
+	fn exit(&mut self, reason: ExitReason, output: Vec<u8>) {
+		// The outermost frame may be exited twice, by the call and by the transaction.
+		let Frame { mut call, snapshot } = match self.frames.pop() {
+			Some(frame) => frame,
+			None => return,
+		};
+		call.gas_used = snapshot
+			.map_or(0, |snapshot| snapshot.used_gas.saturating_add(snapshot.memory_gas))
+			.into();
+		call.exit_reason = Some(reason);
+		call.output = output;
+		match self.frames.last_mut() {
+			Some(parent) => parent.call.calls.push(call),
+			None => self.root = Some(call),
+		}
+	}
+}
+
+fn gas_left(snapshot: &Snapshot) -> u64 {
+	snapshot
+		.gas_limit
+		.saturating_sub(snapshot.used_gas)
+		.saturating_sub(snapshot.memory_gas)
+}
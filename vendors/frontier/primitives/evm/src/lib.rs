@@ -18,6 +18,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod precompile;
+mod trace;
 
 use codec::{Decode, Encode};
 pub use evm::ExitReason;
@@ -31,6 +32,7 @@ pub use precompile::{
 	Context, ExitError, ExitRevert, ExitSucceed, LinearCostPrecompile, Precompile,
 	PrecompileFailure, PrecompileOutput, PrecompileResult, PrecompileSet,
 };
+pub use trace::{CallTrace, CallType, RawTrace, StepLog, TracerConfig, TransactionTrace};
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Default)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+// This file is part of Frontier.
+//
+// Copyright (c) 2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::{Decode, Encode};
+use evm::ExitReason;
+use sp_core::{H160, H256, U256};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+/// The tracer to re-execute a transaction with.
+pub enum TracerConfig {
+	/// Log every executed opcode, like geth's struct logger.
+	Raw { disable_storage: bool, disable_memory: bool, disable_stack: bool },
+	/// Record the tree of calls, like geth's `callTracer`.
+	CallTracer,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+/// The trace of a transaction, in the format of the tracer it was executed with.
+pub enum TransactionTrace {
+	Raw(RawTrace),
+	CallTracer(CallTrace),
+}
+
+impl TransactionTrace {
+	/// Set the gas used by the whole transaction, intrinsic cost and refund included.
+	pub fn set_gas_used(&mut self, gas_used: U256) {
+		match self {
+			TransactionTrace::Raw(trace) => trace.gas_used = gas_used,
+			TransactionTrace::CallTracer(trace) => trace.gas_used = gas_used,
+		}
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct RawTrace {
+	pub gas_used: U256,
+	/// Why the transaction exited, if it executed at all.
+	pub exit_reason: Option<ExitReason>,
+	pub return_value: Vec<u8>,
+	pub step_logs: Vec<StepLog>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+/// The state of the EVM before it executed an opcode.
+pub struct StepLog {
+	pub pc: u64,
+	pub opcode: u8,
+	/// The gas left.
+	pub gas: u64,
+	pub gas_cost: u64,
+	/// The depth of the call frame, 1 for the transaction itself.
+	pub depth: u32,
+	pub stack: Option<Vec<H256>>,
+	pub memory: Option<Vec<u8>>,
+	/// The storage of the executing contract read or written so far.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+	SelfDestruct,
+}
+
+impl Default for CallType {
+	fn default() -> Self {
+		CallType::Call
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+/// A call frame, with the calls it made.
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	/// Why the call exited, which isn't recorded for self-destructs.
+	pub exit_reason: Option<ExitReason>,
+	pub calls: Vec<CallTrace>,
+}
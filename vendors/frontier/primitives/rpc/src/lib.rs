@@ -184,6 +184,22 @@ sp_api::decl_runtime_apis! {
 		#[changed_in(2)]
 		fn convert_transaction(transaction: ethereum::TransactionV0) -> <Block as BlockT>::Extrinsic;
 	}
+
+	/// API re-executing the Ethereum transactions of a block with an EVM tracer, on top of the
+	/// block initialized from its parent.
+	pub trait DebugRuntimeApi {
+		/// Apply `extrinsics` up to the Ethereum transaction `transaction_hash`, and trace it.
+		fn trace_transaction(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			transaction_hash: H256,
+			config: fp_evm::TracerConfig,
+		) -> Result<fp_evm::TransactionTrace, sp_runtime::DispatchError>;
+		/// Apply `extrinsics`, tracing each of their Ethereum transactions.
+		fn trace_block(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			config: fp_evm::TracerConfig,
+		) -> Result<Vec<fp_evm::TransactionTrace>, sp_runtime::DispatchError>;
+	}
 }
 
 pub trait ConvertTransaction<E> {